@@ -14,14 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use canvas_runtime::{AccountId, AuraId, Signature, EXISTENTIAL_DEPOSIT};
+use canvas_runtime::{
+	relay, AccountId, AuraId, Balance, BlockNumber, Signature, EXISTENTIAL_DEPOSIT,
+};
 use cumulus_primitives_core::ParaId;
 use hex_literal::hex;
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
 use sc_service::ChainType;
 use serde::{Deserialize, Serialize};
-use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public};
+use sp_core::{crypto::UncheckedInto, sr25519, Bytes, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::path::{Path, PathBuf};
 
 /// The ID of this Parachain as registered on the Relay Chain.
 ///
@@ -49,6 +52,9 @@ pub struct Extensions {
 	pub relay_chain: String,
 	/// The id of the Parachain.
 	pub para_id: u32,
+	/// The contracts to instantiate in the genesis, see [`from_json_file`].
+	#[serde(default)]
+	pub genesis_contracts: Vec<GenesisContract>,
 }
 
 impl Extensions {
@@ -58,6 +64,210 @@ impl Extensions {
 	}
 }
 
+/// A relay chain Canvas can be connected to.
+///
+/// The runtime targets one of them at compile time, see `runtime/src/relay.rs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Relay {
+	/// The default, targeted without any of the relay chain features.
+	Rococo,
+	/// Targeted with the `westend` feature.
+	Westend,
+	/// Targeted with the `kusama` feature.
+	Kusama,
+	/// Targeted with the `polkadot` feature.
+	Polkadot,
+}
+
+impl Relay {
+	/// The relay chain targeted by the runtime.
+	pub fn of_runtime() -> Self {
+		relay::RELAY_CHAIN
+			.parse()
+			.expect("the runtime targets a known relay chain; qed")
+	}
+
+	/// Fail if the runtime targets another relay chain, its SS58 prefix and XCM network would not
+	/// match the ones of this relay chain.
+	pub fn ensure_runtime(self) -> Result<(), String> {
+		let runtime = Self::of_runtime();
+		match self {
+			_ if self == runtime => Ok(()),
+			Self::Rococo => Err(format!(
+				"The runtime targets {}, build it without the relay chain features for rococo",
+				runtime.id(),
+			)),
+			_ => Err(format!(
+				"The runtime targets {}, build it with the `{}` feature for {}",
+				runtime.id(),
+				self.id(),
+				self.id(),
+			)),
+		}
+	}
+
+	/// The chain id of the relay chain.
+	pub fn id(self) -> &'static str {
+		match self {
+			Self::Rococo => relay::rococo::RELAY_CHAIN,
+			Self::Westend => relay::westend::RELAY_CHAIN,
+			Self::Kusama => relay::kusama::RELAY_CHAIN,
+			Self::Polkadot => relay::polkadot::RELAY_CHAIN,
+		}
+	}
+
+	/// The chain id of a local test network of the relay chain.
+	pub fn local_id(self) -> String {
+		format!("{}-local", self.id())
+	}
+
+	/// The symbol of the native token.
+	pub fn token_symbol(self) -> &'static str {
+		match self {
+			Self::Rococo => relay::rococo::TOKEN_SYMBOL,
+			Self::Westend => relay::westend::TOKEN_SYMBOL,
+			Self::Kusama => relay::kusama::TOKEN_SYMBOL,
+			Self::Polkadot => relay::polkadot::TOKEN_SYMBOL,
+		}
+	}
+
+	/// The decimals of the native token.
+	pub fn token_decimals(self) -> u8 {
+		match self {
+			Self::Rococo => relay::rococo::TOKEN_DECIMALS,
+			Self::Westend => relay::westend::TOKEN_DECIMALS,
+			Self::Kusama => relay::kusama::TOKEN_DECIMALS,
+			Self::Polkadot => relay::polkadot::TOKEN_DECIMALS,
+		}
+	}
+
+	/// The SS58 prefix of addresses.
+	pub fn ss58_format(self) -> u16 {
+		match self {
+			Self::Rococo => relay::rococo::SS58_PREFIX,
+			Self::Westend => relay::westend::SS58_PREFIX,
+			Self::Kusama => relay::kusama::SS58_PREFIX,
+			Self::Polkadot => relay::polkadot::SS58_PREFIX,
+		}
+	}
+
+	/// The properties of a chain connected to this relay chain.
+	pub fn properties(self) -> sc_chain_spec::Properties {
+		let mut properties = sc_chain_spec::Properties::new();
+		properties.insert("tokenSymbol".into(), self.token_symbol().into());
+		properties.insert("tokenDecimals".into(), self.token_decimals().into());
+		properties.insert("ss58Format".into(), self.ss58_format().into());
+		properties
+	}
+}
+
+impl std::str::FromStr for Relay {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"rococo" => Ok(Self::Rococo),
+			"westend" => Ok(Self::Westend),
+			"kusama" => Ok(Self::Kusama),
+			"polkadot" => Ok(Self::Polkadot),
+			_ => Err(format!("Unknown relay chain {}", s)),
+		}
+	}
+}
+
+/// A contract to instantiate in the genesis, as listed in the chain spec.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisContract {
+	/// The path of the Wasm blob, relative to the chain spec.
+	#[serde(default)]
+	pub path: Option<PathBuf>,
+	/// The Wasm blob itself, if no `path` is given.
+	#[serde(default)]
+	pub code: Option<Bytes>,
+	/// The selector of the constructor.
+	pub constructor: Bytes,
+	/// The SCALE encoded arguments of the constructor.
+	#[serde(default)]
+	pub args: Bytes,
+	/// The account which instantiates the contract. It has to be endowed.
+	pub deployer: AccountId,
+	/// The balance transferred to the contract.
+	#[serde(default)]
+	pub endowment: Balance,
+	/// The salt of the contract address.
+	#[serde(default)]
+	pub salt: Bytes,
+}
+
+impl GenesisContract {
+	/// Turn this into the genesis config of `pallet-genesis-contracts`, reading the Wasm blob
+	/// relative to `base`.
+	fn resolve(
+		self,
+		base: &Path,
+	) -> Result<pallet_genesis_contracts::GenesisContract<AccountId, Balance>, String> {
+		let code = match (self.path, self.code) {
+			(Some(path), None) => {
+				let path = base.join(path);
+				std::fs::read(&path)
+					.map_err(|e| format!("Error reading {}: {}", path.display(), e))?
+			},
+			(None, Some(code)) => code.0,
+			_ => return Err("A genesis contract needs either a `path` or its `code`".into()),
+		};
+		if self.constructor.len() != 4 {
+			return Err(format!("Invalid constructor selector {:?}", self.constructor))
+		}
+
+		Ok(pallet_genesis_contracts::GenesisContract {
+			deployer: self.deployer,
+			code,
+			data: [self.constructor.0, self.args.0].concat(),
+			endowment: self.endowment,
+			salt: self.salt.0,
+		})
+	}
+}
+
+/// Load a [`ChainSpec`] from a JSON file.
+///
+/// The contracts listed in the `genesis_contracts` extension are deployed, see
+/// [`with_genesis_contracts`].
+pub fn from_json_file(path: PathBuf) -> Result<ChainSpec, String> {
+	let spec = ChainSpec::from_json_file(path.clone())?;
+	with_genesis_contracts(spec, path.parent().unwrap_or_else(|| Path::new(".")))
+}
+
+/// Add the contracts listed in the `genesis_contracts` extension of a plain chain spec to the
+/// genesis config of `pallet-genesis-contracts`, replacing the ones already in there.
+///
+/// Relative paths of Wasm blobs are resolved against `base`. Raw chain specs are returned as is,
+/// their storage already contains the contracts.
+fn with_genesis_contracts(spec: ChainSpec, base: &Path) -> Result<ChainSpec, String> {
+	let contracts = match Extensions::try_get(&spec) {
+		Some(extensions) if !extensions.genesis_contracts.is_empty() =>
+			extensions.genesis_contracts.clone(),
+		_ => return Ok(spec),
+	};
+
+	let mut json: serde_json::Value =
+		serde_json::from_str(&spec.as_json(false)?).map_err(|e| e.to_string())?;
+	let runtime = match json.pointer_mut("/genesis/runtime") {
+		Some(runtime) => runtime,
+		None => return Ok(spec),
+	};
+
+	let contracts = contracts
+		.into_iter()
+		.map(|contract| contract.resolve(base))
+		.collect::<Result<Vec<_>, _>>()?;
+	runtime["genesisContracts"] = serde_json::json!({ "contracts": contracts });
+
+	ChainSpec::from_json_bytes(serde_json::to_vec(&json).map_err(|e| e.to_string())?)
+}
+
 type AccountPublic = <Signature as Verify>::Signer;
 
 /// Generate collator keys from seed.
@@ -82,12 +292,9 @@ pub fn canvas_session_keys(keys: AuraId) -> canvas_runtime::SessionKeys {
 	canvas_runtime::SessionKeys { aura: keys }
 }
 
-pub fn development_config() -> ChainSpec {
+pub fn development_config(relay: Relay) -> ChainSpec {
 	// Give your base currency a unit name and decimal places
-	let mut properties = sc_chain_spec::Properties::new();
-	properties.insert("tokenSymbol".into(), "ROC".into());
-	properties.insert("tokenDecimals".into(), 12.into());
-	properties.insert("ss58Format".into(), 42.into());
+	let properties = relay.properties();
 
 	ChainSpec::from_genesis(
 		// Name
@@ -122,27 +329,42 @@ pub fn development_config() -> ChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Council and technical committee members.
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
 				PARA_ID.into(),
+				// Sudo key, only used if the runtime is built with the `sudo` feature.
+				Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+				// Code uploaders, only used with the `permissioned-code` feature.
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+				// Faucet, only used with the `faucet` feature.
+				Some(Faucet::local()),
 			)
 		},
+		// Bootnodes
 		Vec::new(),
+		// Telemetry
 		None,
+		// Protocol ID
 		None,
+		// Fork ID
 		None,
-		None,
+		// Properties
+		Some(properties),
+		// Extensions
 		Extensions {
-			relay_chain: "rococo-local".into(), // You MUST set this to the correct network!
+			relay_chain: relay.local_id(),
 			para_id: PARA_ID,
+			genesis_contracts: Vec::new(),
 		},
 	)
 }
 
-pub fn local_testnet_config() -> ChainSpec {
+pub fn local_testnet_config(relay: Relay) -> ChainSpec {
 	// Give your base currency a unit name and decimal places
-	let mut properties = sc_chain_spec::Properties::new();
-	properties.insert("tokenSymbol".into(), "ROC".into());
-	properties.insert("tokenDecimals".into(), 12.into());
-	properties.insert("ss58Format".into(), 42.into());
+	let properties = relay.properties();
 
 	ChainSpec::from_genesis(
 		// Name
@@ -177,7 +399,18 @@ pub fn local_testnet_config() -> ChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Council and technical committee members.
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
 				PARA_ID.into(),
+				// Sudo key, only used if the runtime is built with the `sudo` feature.
+				Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+				// Code uploaders, only used with the `permissioned-code` feature.
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+				// Faucet, only used with the `faucet` feature.
+				Some(Faucet::local()),
 			)
 		},
 		// Bootnodes
@@ -192,17 +425,16 @@ pub fn local_testnet_config() -> ChainSpec {
 		Some(properties),
 		// Extensions
 		Extensions {
-			relay_chain: "rococo-local".into(), // You MUST set this to the correct network!
+			relay_chain: relay.local_id(),
 			para_id: PARA_ID,
+			genesis_contracts: Vec::new(),
 		},
 	)
 }
 
 pub fn rococo_testnet_config() -> ChainSpec {
 	// Give your base currency a unit name and decimal places
-	let mut properties = sc_chain_spec::Properties::new();
-	properties.insert("tokenSymbol".into(), "ROC".into());
-	properties.insert("tokenDecimals".into(), 12.into());
+	let properties = Relay::Rococo.properties();
 
 	ChainSpec::from_genesis(
 		// Name
@@ -259,7 +491,13 @@ pub fn rococo_testnet_config() -> ChainSpec {
 					// AccountId of an account which `ink-waterfall` uses for automated testing
 					hex!["0e47e2344d523c3cc5c34394b0d58b9a4200e813a038e6c5a6163cc07d70b069"].into(),
 				],
+				// The council and technical committee are set through a public referendum.
+				Vec::new(),
 				PARA_ID.into(),
+				None,
+				Vec::new(),
+				// No faucet on a live chain.
+				None,
 			)
 		},
 		// Bootnodes
@@ -286,14 +524,191 @@ pub fn rococo_testnet_config() -> ChainSpec {
 		// Properties
 		Some(properties),
 		// Extensions
-		Extensions { relay_chain: "rococo".into(), para_id: PARA_ID },
+		Extensions {
+			relay_chain: Relay::Rococo.id().into(),
+			para_id: PARA_ID,
+			genesis_contracts: Vec::new(),
+		},
 	)
 }
 
+/// The config of a network, from which `generate-spec` builds its [`ChainSpec`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+	/// The name of the chain.
+	pub name: String,
+	/// The id of the chain.
+	pub id: String,
+	/// The type of the chain.
+	#[serde(default)]
+	pub chain_type: ChainType,
+	/// The id of the network protocol, defaults to the id of the chain.
+	#[serde(default)]
+	pub protocol_id: Option<String>,
+	/// The bootnodes of the chain.
+	#[serde(default)]
+	pub boot_nodes: Vec<sc_service::config::MultiaddrWithPeerId>,
+	/// The relay chain network, defaults to the one targeted by the runtime.
+	#[serde(default = "Relay::of_runtime")]
+	pub relay: Relay,
+	/// The chain id of the relay chain, defaults to the one of the live `relay` network.
+	#[serde(default)]
+	pub relay_chain: Option<String>,
+	/// The id of the Parachain.
+	pub para_id: u32,
+	/// The symbol of the native token, defaults to the one of the `relay`.
+	#[serde(default)]
+	pub token_symbol: Option<String>,
+	/// The decimals of the native token, defaults to the ones of the `relay`.
+	#[serde(default)]
+	pub token_decimals: Option<u8>,
+	/// The SS58 prefix of addresses, defaults to the one of the `relay`.
+	#[serde(default)]
+	pub ss58_format: Option<u16>,
+	/// The invulnerable collators, which author blocks from the genesis on. At least one is
+	/// needed, otherwise no blocks are authored.
+	pub invulnerables: Vec<Invulnerable>,
+	/// The accounts endowed in the genesis.
+	#[serde(default)]
+	pub endowed_accounts: Vec<AccountId>,
+	/// The members of the council and the technical committee.
+	#[serde(default)]
+	pub council: Vec<AccountId>,
+	/// The sudo key, only used if the runtime is built with the `sudo` feature.
+	#[serde(default)]
+	pub sudo: Option<AccountId>,
+	/// The code uploaders, only used with the `permissioned-code` feature.
+	#[serde(default)]
+	pub code_uploaders: Vec<AccountId>,
+	/// The contracts to instantiate in the genesis.
+	#[serde(default)]
+	pub genesis_contracts: Vec<GenesisContract>,
+	/// The faucet, only used with the `faucet` feature. Without it, the faucet pot is empty.
+	#[serde(default)]
+	pub faucet: Option<Faucet>,
+}
+
+/// The faucet of a [`NetworkConfig`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Faucet {
+	/// The initial balance of the faucet pot.
+	pub pot_balance: Balance,
+	/// The amount handed out per claim.
+	pub claim_amount: Balance,
+	/// The number of blocks an account has to wait between two claims.
+	pub claim_period: BlockNumber,
+}
+
+impl Faucet {
+	/// The faucet of the `dev` and `local` chain specs.
+	pub fn local() -> Self {
+		Self {
+			pot_balance: 1 << 60,
+			claim_amount: 100 * canvas_runtime::UNIT,
+			claim_period: canvas_runtime::HOURS,
+		}
+	}
+}
+
+/// An invulnerable collator of a [`NetworkConfig`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Invulnerable {
+	/// The account of the collator.
+	pub account: AccountId,
+	/// The Aura session key of the collator.
+	pub aura: AuraId,
+}
+
+impl NetworkConfig {
+	/// Read the config from a TOML file or, for any other extension, from a JSON file.
+	pub fn from_file(path: &Path) -> Result<Self, String> {
+		let content = std::fs::read_to_string(path)
+			.map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+		if path.extension().map_or(false, |extension| extension == "toml") {
+			toml::from_str(&content).map_err(|e| format!("Invalid config: {}", e))
+		} else {
+			serde_json::from_str(&content).map_err(|e| format!("Invalid config: {}", e))
+		}
+	}
+}
+
+/// Build a [`ChainSpec`] from the config of a network.
+///
+/// Relative paths of genesis contracts are resolved against `base`.
+pub fn from_config(config: NetworkConfig, base: &Path) -> Result<ChainSpec, String> {
+	let relay = config.relay;
+	relay.ensure_runtime()?;
+	if config.invulnerables.is_empty() {
+		return Err("The config needs at least one invulnerable collator".into())
+	}
+
+	let mut properties = relay.properties();
+	if let Some(token_symbol) = config.token_symbol {
+		properties.insert("tokenSymbol".into(), token_symbol.into());
+	}
+	if let Some(token_decimals) = config.token_decimals {
+		properties.insert("tokenDecimals".into(), token_decimals.into());
+	}
+	if let Some(ss58_format) = config.ss58_format {
+		properties.insert("ss58Format".into(), ss58_format.into());
+	}
+
+	let invulnerables: Vec<_> =
+		config.invulnerables.into_iter().map(|i| (i.account, i.aura)).collect();
+	let (endowed_accounts, council, root_key, code_uploaders, faucet) = (
+		config.endowed_accounts,
+		config.council,
+		config.sudo,
+		config.code_uploaders,
+		config.faucet,
+	);
+	let para_id = config.para_id;
+	let protocol_id = config.protocol_id.unwrap_or_else(|| config.id.clone());
+
+	let spec = ChainSpec::from_genesis(
+		&config.name,
+		&config.id,
+		config.chain_type,
+		move || {
+			canvas_genesis(
+				invulnerables.clone(),
+				endowed_accounts.clone(),
+				council.clone(),
+				para_id.into(),
+				root_key.clone(),
+				code_uploaders.clone(),
+				faucet.clone(),
+			)
+		},
+		config.boot_nodes,
+		None,
+		Some(&protocol_id),
+		None,
+		Some(properties),
+		Extensions {
+			relay_chain: config.relay_chain.unwrap_or_else(|| relay.id().into()),
+			para_id,
+			genesis_contracts: config.genesis_contracts,
+		},
+	);
+	with_genesis_contracts(spec, base)
+}
+
+#[cfg_attr(
+	not(all(feature = "sudo", feature = "permissioned-code", feature = "faucet")),
+	allow(unused_variables)
+)]
 fn canvas_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
+	council: Vec<AccountId>,
 	id: ParaId,
+	root_key: Option<AccountId>,
+	code_uploaders: Vec<AccountId>,
+	faucet: Option<Faucet>,
 ) -> canvas_runtime::GenesisConfig {
 	canvas_runtime::GenesisConfig {
 		system: canvas_runtime::SystemConfig {
@@ -330,5 +745,31 @@ fn canvas_genesis(
 		polkadot_xcm: canvas_runtime::PolkadotXcmConfig {
 			safe_xcm_version: Some(SAFE_XCM_VERSION),
 		},
+		council: canvas_runtime::CouncilConfig {
+			members: council.clone(),
+			phantom: Default::default(),
+		},
+		technical_committee: canvas_runtime::TechnicalCommitteeConfig {
+			members: council,
+			phantom: Default::default(),
+		},
+		democracy: Default::default(),
+		// Listed in the `genesis_contracts` extension of chain spec files instead.
+		genesis_contracts: Default::default(),
+		#[cfg(feature = "sudo")]
+		sudo: canvas_runtime::SudoConfig { key: root_key },
+		#[cfg(feature = "permissioned-code")]
+		code_permissions: canvas_runtime::CodePermissionsConfig {
+			uploaders: code_uploaders,
+			approved_code: Vec::new(),
+		},
+		#[cfg(feature = "faucet")]
+		faucet: faucet
+			.map(|faucet| canvas_runtime::FaucetConfig {
+				pot_balance: faucet.pot_balance,
+				claim_amount: faucet.claim_amount,
+				claim_period: faucet.claim_period,
+			})
+			.unwrap_or_default(),
 	}
 }
@@ -14,9 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::chain_spec;
+use crate::{chain_spec, rpc::RpcGroup};
 use clap::{AppSettings, Parser};
-use std::path::PathBuf;
+use std::{collections::HashSet, path::PathBuf};
 
 /// Sub-commands supported by the collator.
 #[derive(Debug, clap::Subcommand)]
@@ -32,6 +32,10 @@ pub enum Subcommand {
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Generate a chain specification from the config of a network.
+	#[clap(name = "generate-spec")]
+	GenerateSpec(GenerateSpecCommand),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
@@ -90,6 +94,22 @@ pub struct ExportGenesisWasmCommand {
 	pub chain: Option<String>,
 }
 
+/// Command for generating a chain specification from a config file.
+#[derive(Debug, Parser)]
+pub struct GenerateSpecCommand {
+	/// The TOML or JSON config of the network.
+	#[clap(parse(from_os_str))]
+	pub config: PathBuf,
+
+	/// Output file name or stdout if unspecified.
+	#[clap(short, long, parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	/// Write the genesis storage instead of the genesis config.
+	#[clap(long)]
+	pub raw: bool,
+}
+
 #[derive(Debug, Parser)]
 #[clap(setting(
 	AppSettings::PropagateVersion |
@@ -103,11 +123,48 @@ pub struct Cli {
 	#[clap(flatten)]
 	pub run: cumulus_client_cli::RunCmd,
 
+	/// Run a development chain as a standalone node which seals its own blocks instead of
+	/// following a relay chain.
+	#[clap(long)]
+	pub standalone: bool,
+
+	/// With `--standalone`, additionally seal a block every given number of milliseconds.
+	/// Without it, blocks are only sealed when transactions arrive.
+	#[clap(long, requires = "standalone")]
+	pub dev_block_time: Option<u64>,
+
+	/// Use the RPC of an external relay chain node at this WebSocket URL instead of running an
+	/// embedded relay chain node. The relay chain arguments are ignored then. Only works for
+	/// full nodes, collators need an embedded relay chain node.
+	#[clap(long, conflicts_with = "collator")]
+	pub relay_chain_rpc_url: Option<url::Url>,
+
+	/// Enable a group of RPC modules which is disabled by default, e.g. `debugging` or
+	/// `indexer`. Can be repeated.
+	#[clap(long = "enable-rpc", value_name = "GROUP")]
+	pub enable_rpc: Vec<RpcGroup>,
+
+	/// Disable a group of RPC modules, e.g. `contracts` or `unsafe`. Can be repeated.
+	#[clap(long = "disable-rpc", value_name = "GROUP")]
+	pub disable_rpc: Vec<RpcGroup>,
+
 	/// Relay chain arguments
 	#[clap(raw = true)]
 	pub relay_chain_args: Vec<String>,
 }
 
+impl Cli {
+	/// The groups of RPC modules enabled on the command line or by default.
+	pub fn rpc_groups(&self) -> HashSet<RpcGroup> {
+		RpcGroup::DEFAULT
+			.iter()
+			.chain(&self.enable_rpc)
+			.filter(|group| !self.disable_rpc.contains(group))
+			.cloned()
+			.collect()
+	}
+}
+
 #[derive(Debug)]
 pub struct RelayChainCli {
 	/// The actual relay chain cli object.
@@ -15,15 +15,15 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	chain_spec,
+	chain_spec::{self, Relay},
 	cli::{Cli, RelayChainCli, Subcommand},
-	service::{new_partial, CanvasRuntimeExecutor},
+	service::{new_partial, CanvasRpcRegistry, CanvasRuntimeExecutor, RelayChain},
 };
 use canvas_runtime::{Block, RuntimeApi};
 use codec::Encode;
 use cumulus_client_service::genesis::generate_genesis_block;
 use cumulus_primitives_core::ParaId;
-use log::info;
+use log::{info, warn};
 use polkadot_parachain::primitives::AccountIdConversion;
 use sc_cli::{
 	ChainSpec, CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams,
@@ -31,18 +31,35 @@ use sc_cli::{
 };
 use sc_service::{
 	config::{BasePath, PrometheusConfig},
-	TaskManager,
+	ChainType, TaskManager,
 };
 use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::traits::Block as BlockT;
-use std::{io::Write, net::SocketAddr};
+use std::{io::Write, net::SocketAddr, time::Duration};
 
 fn load_spec(id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
 	Ok(match id {
-		"dev" => Box::new(chain_spec::development_config()),
-		"canvas-rococo" => Box::new(chain_spec::rococo_testnet_config()),
-		"" | "local" => Box::new(chain_spec::local_testnet_config()),
-		path => Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
+		"dev" => Box::new(chain_spec::development_config(Relay::of_runtime())),
+		"canvas-rococo" => {
+			Relay::Rococo.ensure_runtime()?;
+			Box::new(chain_spec::rococo_testnet_config())
+		},
+		"" | "local" => Box::new(chain_spec::local_testnet_config(Relay::of_runtime())),
+		// `<relay>-dev` and `<relay>-local` target a local network of the given relay chain.
+		id => match id
+			.rsplit_once('-')
+			.and_then(|(relay, kind)| Some((relay.parse::<Relay>().ok()?, kind)))
+		{
+			Some((relay, "dev")) => {
+				relay.ensure_runtime()?;
+				Box::new(chain_spec::development_config(relay))
+			},
+			Some((relay, "local")) => {
+				relay.ensure_runtime()?;
+				Box::new(chain_spec::local_testnet_config(relay))
+			},
+			_ => Box::new(chain_spec::from_json_file(std::path::PathBuf::from(id))?),
+		},
 	})
 }
 
@@ -154,6 +171,11 @@ macro_rules! construct_async_run {
 
 /// Parse command line arguments into service configuration.
 pub fn run() -> Result<()> {
+	run_with_rpc(|_| {})
+}
+
+/// Like [`run`], but lets crates embedding the node register additional RPC modules.
+pub fn run_with_rpc(register_rpc: impl FnOnce(&mut CanvasRpcRegistry)) -> Result<()> {
 	let cli = Cli::from_args();
 
 	match &cli.subcommand {
@@ -161,6 +183,20 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		},
+		Some(Subcommand::GenerateSpec(params)) => {
+			let config = chain_spec::NetworkConfig::from_file(&params.config)?;
+			let base = params.config.parent().unwrap_or_else(|| std::path::Path::new("."));
+			let spec = chain_spec::from_config(config, base)?;
+			let json = sc_service::chain_ops::build_spec(&spec, params.raw)?;
+
+			if let Some(output) = &params.output {
+				std::fs::write(output, json)?;
+			} else {
+				std::io::stdout().write_all(json.as_bytes())?;
+			}
+
+			Ok(())
+		},
 		Some(Subcommand::CheckBlock(cmd)) => {
 			construct_async_run!(|components, cli, cmd, config| {
 				Ok(cmd.run(components.client, components.import_queue))
@@ -280,18 +316,26 @@ pub fn run() -> Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run.normalize())?;
+			let mut rpc = CanvasRpcRegistry::with_defaults(cli.rpc_groups());
+			register_rpc(&mut rpc);
+			let standalone = cli.standalone;
+			let dev_block_time = cli.dev_block_time.map(Duration::from_millis);
 
 			runner.run_node_until_exit(|config| async move {
-				let _ = &cli;
+				if standalone {
+					if config.chain_spec.chain_type() != ChainType::Development {
+						return Err("Only development chains can run standalone.".into())
+					}
+					info!("Standalone node, sealing blocks without a relay chain");
+
+					return crate::service::start_dev_node(config, dev_block_time, rpc)
+						.map_err(Into::into)
+				}
+
 				let para_id = chain_spec::Extensions::try_get(&*config.chain_spec)
 					.map(|e| e.para_id)
 					.ok_or("Could not find parachain ID in chain-spec.")?;
 
-				let polkadot_cli = RelayChainCli::new(
-					&config,
-					[RelayChainCli::executable_name()].iter().chain(cli.relay_chain_args.iter()),
-				);
-
 				let id = ParaId::from(para_id);
 
 				let parachain_account =
@@ -304,17 +348,40 @@ pub fn run() -> Result<()> {
 
 				let genesis_state = format!("0x{:?}", HexDisplay::from(&block.header().encode()));
 
-				let tokio_handle = config.tokio_handle.clone();
-				let polkadot_config =
-					SubstrateCli::create_configuration(&polkadot_cli, &polkadot_cli, tokio_handle)
+				// With an external relay chain node there is no need for a relay chain database
+				// and networking, so the relay chain arguments are not even parsed.
+				let relay_chain = match &cli.relay_chain_rpc_url {
+					Some(url) => {
+						if !cli.relay_chain_args.is_empty() {
+							warn!("Ignoring the relay chain arguments, using the relay chain RPC");
+						}
+						info!("Relay chain RPC: {}", url);
+						RelayChain::Rpc(url.clone())
+					},
+					None => {
+						let polkadot_cli = RelayChainCli::new(
+							&config,
+							[RelayChainCli::executable_name()]
+								.iter()
+								.chain(cli.relay_chain_args.iter()),
+						);
+						let tokio_handle = config.tokio_handle.clone();
+						let polkadot_config = SubstrateCli::create_configuration(
+							&polkadot_cli,
+							&polkadot_cli,
+							tokio_handle,
+						)
 						.map_err(|err| format!("Relay chain argument error: {}", err))?;
+						RelayChain::Embedded(polkadot_config)
+					},
+				};
 
 				info!("Parachain id: {:?}", id);
 				info!("Parachain Account: {}", parachain_account);
 				info!("Parachain genesis state: {}", genesis_state);
 				info!("Is collating: {}", if config.role.is_authority() { "yes" } else { "no" });
 
-				crate::service::start_parachain_node(config, polkadot_config, id)
+				crate::service::start_parachain_node(config, relay_chain, id, rpc)
 					.await
 					.map(|r| r.0)
 					.map_err(Into::into)
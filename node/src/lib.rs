@@ -15,5 +15,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 pub mod chain_spec;
-mod rpc;
+pub mod cli;
+pub mod command;
+pub mod rpc;
 pub mod service;
@@ -21,7 +21,7 @@
 
 #![warn(missing_docs)]
 
-use std::sync::Arc;
+use std::{borrow::Cow, collections::HashSet, fmt, str::FromStr, sync::Arc};
 
 use canvas_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index as Nonce};
 use pallet_contracts_rpc::{Contracts, ContractsApi};
@@ -46,8 +46,105 @@ pub struct FullDeps<C, P> {
 	pub deny_unsafe: DenyUnsafe,
 }
 
-/// Instantiate all RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> RpcExtension
+/// A group of RPC modules, which node operators enable or disable as a whole.
+///
+/// The `system` and `payment` RPCs are always available. Besides the groups defined here,
+/// crates embedding the node can register modules in groups of their own.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RpcGroup(Cow<'static, str>);
+
+impl RpcGroup {
+	/// Calling contracts and reading their storage, e.g. `contracts_call`.
+	pub const CONTRACTS: RpcGroup = RpcGroup::new("contracts");
+	/// Modules for debugging, e.g. tracing calls. Disabled by default.
+	pub const DEBUGGING: RpcGroup = RpcGroup::new("debugging");
+	/// Modules serving indexers, which are expensive to serve. Disabled by default.
+	pub const INDEXER: RpcGroup = RpcGroup::new("indexer");
+	/// Modules which must not be exposed publicly, e.g. `engine_createBlock` of the development
+	/// node. They are only available where `--rpc-methods` allows unsafe RPCs.
+	pub const UNSAFE: RpcGroup = RpcGroup::new("unsafe");
+
+	/// The groups enabled unless disabled on the command line.
+	pub const DEFAULT: [RpcGroup; 2] = [RpcGroup::CONTRACTS, RpcGroup::UNSAFE];
+
+	/// A group with the given name, which is used on the command line.
+	pub const fn new(name: &'static str) -> Self {
+		Self(Cow::Borrowed(name))
+	}
+
+	/// The name of the group.
+	pub fn name(&self) -> &str {
+		&self.0
+	}
+}
+
+impl FromStr for RpcGroup {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		if name.is_empty() {
+			return Err("The name of an RPC group can't be empty".into())
+		}
+		Ok(Self(Cow::Owned(name.into())))
+	}
+}
+
+impl fmt::Display for RpcGroup {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+/// Adds the modules of a group to the RPC extension.
+pub type RpcModuleBuilder<C, P> = Box<dyn Fn(&mut RpcExtension, &FullDeps<C, P>) + Send + Sync>;
+
+/// The RPC modules of the node by group, only the ones of enabled groups are instantiated.
+///
+/// Crates embedding the node register their own modules here.
+pub struct RpcRegistry<C, P> {
+	enabled: HashSet<RpcGroup>,
+	modules: Vec<(RpcGroup, RpcModuleBuilder<C, P>)>,
+}
+
+impl<C, P> RpcRegistry<C, P> {
+	/// Create an empty registry with the given groups enabled.
+	pub fn new(enabled: HashSet<RpcGroup>) -> Self {
+		Self { enabled, modules: Vec::new() }
+	}
+
+	/// Register modules of the given group.
+	pub fn register(
+		&mut self,
+		group: RpcGroup,
+		builder: impl Fn(&mut RpcExtension, &FullDeps<C, P>) + Send + Sync + 'static,
+	) {
+		self.modules.push((group, Box::new(builder)));
+	}
+
+	/// Whether the modules of `group` are instantiated for an RPC server with `deny_unsafe`.
+	pub fn is_enabled(&self, group: &RpcGroup, deny_unsafe: DenyUnsafe) -> bool {
+		self.enabled.contains(group) &&
+			!(*group == RpcGroup::UNSAFE && matches!(deny_unsafe, DenyUnsafe::Yes))
+	}
+}
+
+impl<C, P> RpcRegistry<C, P>
+where
+	C: ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+{
+	/// Create a registry with the modules of this node and the given groups enabled.
+	pub fn with_defaults(enabled: HashSet<RpcGroup>) -> Self {
+		let mut registry = Self::new(enabled);
+		registry.register(RpcGroup::CONTRACTS, |io, deps| {
+			io.extend_with(ContractsApi::to_delegate(Contracts::new(deps.client.clone())));
+		});
+		registry
+	}
+}
+
+/// Instantiate all RPC extensions of the enabled groups.
+pub fn create_full<C, P>(deps: FullDeps<C, P>, registry: &RpcRegistry<C, P>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>
 		+ HeaderBackend<Block>
@@ -58,7 +155,6 @@ where
 		+ 'static,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
-	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
@@ -66,11 +162,21 @@ where
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, deny_unsafe } = deps;
 
-	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
-	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
-	io.extend_with(ContractsApi::to_delegate(Contracts::new(client)));
+	io.extend_with(SystemApi::to_delegate(FullSystem::new(
+		deps.client.clone(),
+		deps.pool.clone(),
+		deps.deny_unsafe,
+	)));
+	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
+		deps.client.clone(),
+	)));
+
+	for (group, builder) in &registry.modules {
+		if registry.is_enabled(group, deps.deny_unsafe) {
+			builder(&mut io, &deps);
+		}
+	}
 
 	io
 }
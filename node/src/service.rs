@@ -17,7 +17,13 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
 // std
-use std::{sync::Arc, time::Duration};
+use std::{
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
 
 // Local Runtime Types
 use canvas_runtime::{
@@ -32,16 +38,28 @@ use cumulus_client_service::{
 	prepare_node_config, start_collator, start_full_node, StartCollatorParams, StartFullNodeParams,
 };
 use cumulus_primitives_core::ParaId;
+use cumulus_primitives_parachain_inherent::MockValidationDataInherentDataProvider;
 use cumulus_relay_chain_interface::RelayChainInterface;
-use cumulus_relay_chain_local::build_relay_chain_interface;
+use cumulus_relay_chain_rpc::RelayChainRPCInterface;
+use polkadot_primitives::v1::CollatorPair;
+
+// Local Imports
+use crate::rpc::{RpcExtension, RpcGroup, RpcRegistry};
 
 // Substrate Imports
+use futures::StreamExt;
 use sc_client_api::ExecutorProvider;
+use sc_consensus_manual_seal::{
+	rpc::{ManualSeal, ManualSealApi},
+	run_manual_seal, EngineCommand, ManualSealParams,
+};
 use sc_executor::NativeElseWasmExecutor;
 use sc_network::NetworkService;
 use sc_service::{Configuration, PartialComponents, Role, TFullBackend, TFullClient, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, TelemetryWorkerHandle};
+use sc_transaction_pool_api::TransactionPool;
 use sp_api::ConstructRuntimeApi;
+use sp_blockchain::HeaderBackend;
 use sp_consensus::SlotData;
 use sp_keystore::SyncCryptoStorePtr;
 use sp_runtime::traits::BlakeTwo256;
@@ -62,6 +80,16 @@ impl sc_executor::NativeExecutionDispatch for CanvasRuntimeExecutor {
 	}
 }
 
+/// The client of a node running the Canvas runtime.
+pub type CanvasClient =
+	TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<CanvasRuntimeExecutor>>;
+
+/// The transaction pool of a node running the Canvas runtime.
+pub type CanvasTransactionPool = sc_transaction_pool::FullPool<Block, CanvasClient>;
+
+/// The RPC modules of a node running the Canvas runtime.
+pub type CanvasRpcRegistry = RpcRegistry<CanvasClient, CanvasTransactionPool>;
+
 /// Starts a `ServiceBuilder` for a full service.
 ///
 /// Use this macro if you don't actually need the full service, but just the builder in order to
@@ -177,15 +205,59 @@ where
 	Ok(params)
 }
 
-/// Start a node with the given parachain `Configuration` and relay chain `Configuration`.
+/// How a parachain node connects to the relay chain.
+pub enum RelayChain {
+	/// Run an embedded relay chain node with the given `Configuration`.
+	Embedded(Configuration),
+	/// Use the RPC of an external relay chain node at the given WebSocket URL.
+	///
+	/// There is no relay chain database or networking then, so this only works for full nodes.
+	/// Collators distribute their collations through the overseer of the relay chain node,
+	/// which isn't available over RPC.
+	Rpc(url::Url),
+}
+
+/// Build the interface to the relay chain.
+///
+/// The collator key is only returned for an embedded relay chain node, which is needed to
+/// announce collations.
+pub async fn build_relay_chain_interface(
+	relay_chain: RelayChain,
+	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
+	task_manager: &mut TaskManager,
+) -> sc_service::error::Result<(Arc<dyn RelayChainInterface>, Option<CollatorPair>)> {
+	match relay_chain {
+		RelayChain::Embedded(polkadot_config) => {
+			let (relay_chain_interface, collator_key) =
+				cumulus_relay_chain_local::build_relay_chain_interface(
+					polkadot_config,
+					telemetry_worker_handle,
+					task_manager,
+				)
+				.map_err(|e| match e {
+					polkadot_service::Error::Sub(x) => x,
+					s => format!("{}", s).into(),
+				})?;
+			Ok((relay_chain_interface, Some(collator_key)))
+		},
+		RelayChain::Rpc(url) => {
+			let relay_chain_interface = RelayChainRPCInterface::new(url)
+				.await
+				.map_err(|e| format!("Error connecting to the relay chain RPC: {}", e))?;
+			Ok((Arc::new(relay_chain_interface) as Arc<_>, None))
+		},
+	}
+}
+
+/// Start a node with the given parachain `Configuration` and connection to the relay chain.
 ///
 /// This is the actual implementation that is abstract over the executor and the runtime api.
 #[sc_tracing::logging::prefix_logs_with("Parachain")]
 async fn start_node_impl<RuntimeApi, Executor, RB, BIQ, BIC>(
 	parachain_config: Configuration,
-	polkadot_config: Configuration,
+	relay_chain: RelayChain,
 	id: ParaId,
-	_rpc_ext_builder: RB,
+	rpc_ext_builder: RB,
 	build_import_queue: BIQ,
 	build_consensus: BIC,
 ) -> sc_service::error::Result<(
@@ -212,8 +284,14 @@ where
 	sc_client_api::StateBackendFor<TFullBackend<Block>, Block>: sp_api::StateBackend<BlakeTwo256>,
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
 	RB: Fn(
-			Arc<TFullClient<Block, RuntimeApi, Executor>>,
-		) -> Result<jsonrpc_core::IoHandler<sc_rpc::Metadata>, sc_service::Error>
+			crate::rpc::FullDeps<
+				TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				sc_transaction_pool::FullPool<
+					Block,
+					TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				>,
+			>,
+		) -> Result<RpcExtension, sc_service::Error>
 		+ Send
 		+ 'static,
 	BIQ: FnOnce(
@@ -248,6 +326,11 @@ where
 	if matches!(parachain_config.role, Role::Light) {
 		return Err("Light client not supported!".into())
 	}
+	if parachain_config.role.is_authority() && matches!(relay_chain, RelayChain::Rpc(_)) {
+		return Err(
+			"Collators need an embedded relay chain node, remove `--relay-chain-rpc-url`".into()
+		)
+	}
 
 	let parachain_config = prepare_node_config(parachain_config);
 
@@ -259,11 +342,8 @@ where
 	let mut task_manager = params.task_manager;
 
 	let (relay_chain_interface, collator_key) =
-		build_relay_chain_interface(polkadot_config, telemetry_worker_handle, &mut task_manager)
-			.map_err(|e| match e {
-				polkadot_service::Error::Sub(x) => x,
-				s => format!("{}", s).into(),
-			})?;
+		build_relay_chain_interface(relay_chain, telemetry_worker_handle, &mut task_manager)
+			.await?;
 
 	let block_announce_validator = BlockAnnounceValidator::new(relay_chain_interface.clone(), id);
 
@@ -296,7 +376,7 @@ where
 				deny_unsafe,
 			};
 
-			Ok(crate::rpc::create_full(deps))
+			rpc_ext_builder(deps)
 		})
 	};
 
@@ -345,7 +425,7 @@ where
 			spawner,
 			parachain_consensus,
 			import_queue,
-			collator_key,
+			collator_key: collator_key.expect("collators embed a relay chain node; qed"),
 			relay_chain_slot_duration,
 		};
 
@@ -416,19 +496,22 @@ pub fn parachain_build_import_queue(
 }
 
 /// Start a parachain node.
+///
+/// The RPC modules of the enabled groups in `rpc` are served next to the built-in ones.
 pub async fn start_parachain_node(
 	parachain_config: Configuration,
-	polkadot_config: Configuration,
+	relay_chain: RelayChain,
 	id: ParaId,
+	rpc: CanvasRpcRegistry,
 ) -> sc_service::error::Result<(
 	TaskManager,
 	Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<CanvasRuntimeExecutor>>>,
 )> {
 	start_node_impl::<RuntimeApi, CanvasRuntimeExecutor, _, _, _>(
 		parachain_config,
-		polkadot_config,
+		relay_chain,
 		id,
-		|_| Ok(Default::default()),
+		move |deps| Ok(crate::rpc::create_full(deps, &rpc)),
 		parachain_build_import_queue,
 		|client,
 		 prometheus_registry,
@@ -475,7 +558,25 @@ pub async fn start_parachain_node(
 									"Failed to create parachain inherent",
 								)
 							})?;
-							Ok((time, slot, parachain_inherent))
+
+							// Without the proof the block is still valid, it just keeps the
+							// previous seed.
+							let relay_randomness = relay_chain_interface
+								.prove_read(
+									relay_parent,
+									&pallet_relay_randomness::relay_storage_keys(),
+								)
+								.await
+								.map_err(|e| {
+									log::warn!("Failed to prove relay chain randomness: {:?}", e)
+								})
+								.ok();
+							let relay_randomness =
+								pallet_relay_randomness::InherentDataProvider::new(
+									relay_randomness,
+								);
+
+							Ok((time, slot, parachain_inherent, relay_randomness))
 						}
 					},
 					block_import: client.clone(),
@@ -496,3 +597,179 @@ pub async fn start_parachain_node(
 	)
 	.await
 }
+
+/// Build the import queue for the standalone development node.
+///
+/// Blocks are sealed locally by manual seal, so there is no consensus to verify on import.
+#[allow(clippy::type_complexity)]
+pub fn dev_build_import_queue(
+	client: Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<CanvasRuntimeExecutor>>>,
+	config: &Configuration,
+	_: Option<TelemetryHandle>,
+	task_manager: &TaskManager,
+) -> Result<
+	sc_consensus::DefaultImportQueue<
+		Block,
+		TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<CanvasRuntimeExecutor>>,
+	>,
+	sc_service::Error,
+> {
+	Ok(sc_consensus_manual_seal::import_queue(
+		Box::new(client),
+		&task_manager.spawn_essential_handle(),
+		config.prometheus_registry(),
+	))
+}
+
+/// Start a standalone development node which does not need a relay chain.
+///
+/// A block is sealed as soon as a transaction enters the pool. If `block_time` is set, an
+/// additional (possibly empty) block is sealed in that interval. Blocks can also be sealed on
+/// demand through the `engine_createBlock` RPC, which belongs to [`RpcGroup::UNSAFE`]. The
+/// parachain inherent is mocked.
+pub fn start_dev_node(
+	config: Configuration,
+	block_time: Option<Duration>,
+	mut rpc: CanvasRpcRegistry,
+) -> sc_service::error::Result<TaskManager> {
+	let params =
+		new_partial::<RuntimeApi, CanvasRuntimeExecutor, _>(&config, dev_build_import_queue)?;
+	let (mut telemetry, _) = params.other;
+
+	let client = params.client.clone();
+	let backend = params.backend.clone();
+	let mut task_manager = params.task_manager;
+	let transaction_pool = params.transaction_pool.clone();
+	let prometheus_registry = config.prometheus_registry().cloned();
+	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+
+	let (network, system_rpc_tx, start_network) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue: params.import_queue,
+			block_announce_validator_builder: None,
+			warp_sync: None,
+		})?;
+
+	let (command_sink, manual_seal_commands) = futures::channel::mpsc::channel(1024);
+
+	let rpc_extensions_builder = {
+		let client = client.clone();
+		let transaction_pool = transaction_pool.clone();
+
+		rpc.register(RpcGroup::UNSAFE, move |io, _| {
+			io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(command_sink.clone())));
+		});
+
+		Box::new(move |deny_unsafe, _| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: transaction_pool.clone(),
+				deny_unsafe,
+			};
+
+			Ok(crate::rpc::create_full(deps, &rpc))
+		})
+	};
+
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		rpc_extensions_builder,
+		client: client.clone(),
+		transaction_pool: transaction_pool.clone(),
+		task_manager: &mut task_manager,
+		config,
+		keystore: params.keystore_container.sync_keystore(),
+		backend,
+		network,
+		system_rpc_tx,
+		telemetry: telemetry.as_mut(),
+	})?;
+
+	let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+		telemetry.as_ref().map(|t| t.handle()),
+	);
+
+	let seal_block = || EngineCommand::SealNewBlock {
+		create_empty: true,
+		finalize: true,
+		parent_hash: None,
+		sender: None,
+	};
+
+	let mut commands_stream = vec![
+		manual_seal_commands.boxed(),
+		transaction_pool.import_notification_stream().map(move |_| seal_block()).boxed(),
+	];
+	if let Some(block_time) = block_time {
+		commands_stream.push(
+			futures::stream::unfold((), move |()| async move {
+				futures_timer::Delay::new(block_time).await;
+				Some((seal_block(), ()))
+			})
+			.boxed(),
+		);
+	}
+
+	// Blocks may be sealed faster than the runtime's `MinimumPeriod` allows, so the timestamp is
+	// advanced by at least one slot per block.
+	let last_timestamp = Arc::new(AtomicU64::new(0));
+
+	let create_inherent_data_providers = {
+		let client = client.clone();
+		move |parent: Hash, ()| {
+			let current_para_block = client.number(parent).ok().flatten();
+			let last_timestamp = last_timestamp.clone();
+
+			async move {
+				let current_para_block = current_para_block.ok_or_else(|| {
+					Box::<dyn std::error::Error + Send + Sync>::from(
+						"Failed to find the parent block number",
+					)
+				})?;
+
+				let previous = last_timestamp.load(Ordering::SeqCst);
+				let timestamp = (*sp_timestamp::Timestamp::current())
+					.max(previous + canvas_runtime::SLOT_DURATION);
+				last_timestamp.store(timestamp, Ordering::SeqCst);
+				let time = sp_timestamp::InherentDataProvider::new(timestamp.into());
+
+				let parachain_inherent = MockValidationDataInherentDataProvider {
+					current_para_block,
+					relay_offset: 1000,
+					relay_blocks_per_para_block: 2,
+					xcm_config: Default::default(),
+					raw_downward_messages: Vec::new(),
+					raw_horizontal_messages: Vec::new(),
+				};
+
+				Ok((time, parachain_inherent))
+			}
+		}
+	};
+
+	task_manager.spawn_essential_handle().spawn_blocking(
+		"manual-seal",
+		Some("block-authoring"),
+		run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env: proposer_factory,
+			client,
+			pool: transaction_pool,
+			commands_stream: futures::stream::select_all(commands_stream),
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers,
+		}),
+	);
+
+	start_network.start_network();
+
+	Ok(task_manager)
+}
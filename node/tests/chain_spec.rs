@@ -0,0 +1,186 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for generating chain specs from network configs.
+
+use canvas_node::chain_spec::{from_config, NetworkConfig, Relay};
+use sp_core::hexdisplay::HexDisplay;
+use std::path::{Path, PathBuf};
+
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+const TOML_CONFIG: &str = r#"
+name = "Canvas Staging"
+id = "canvas_staging"
+chain_type = "Live"
+para_id = 2000
+token_symbol = "CAN"
+endowed_accounts = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+
+[[invulnerables]]
+account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+aura = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+
+[[genesis_contracts]]
+path = "contracts/noop.wasm"
+constructor = "0x9bae9d5e"
+deployer = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+"#;
+
+const JSON_CONFIG: &str = r#"{
+	"name": "Canvas Staging",
+	"id": "canvas_staging",
+	"chain_type": "Live",
+	"para_id": 2000,
+	"token_symbol": "CAN",
+	"endowed_accounts": ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"],
+	"invulnerables": [{
+		"account": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+		"aura": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+	}],
+	"genesis_contracts": [{
+		"path": "contracts/noop.wasm",
+		"constructor": "0x9bae9d5e",
+		"deployer": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+	}]
+}"#;
+
+/// The code of the genesis contract, only read and not instantiated by these tests.
+const CODE: &[u8] = b"\0asm\x01\0\0\0";
+
+/// A fresh directory with `config` in `file` and [`CODE`] in `contracts/noop.wasm`.
+fn network_dir(test: &str, file: &str, config: &str) -> PathBuf {
+	let dir =
+		std::env::temp_dir().join(format!("canvas-chain-spec-{}-{}", test, std::process::id()));
+	let _ = std::fs::remove_dir_all(&dir);
+	std::fs::create_dir_all(dir.join("contracts")).unwrap();
+	std::fs::write(dir.join(file), config).unwrap();
+	std::fs::write(dir.join("contracts/noop.wasm"), CODE).unwrap();
+	dir
+}
+
+fn read_config(dir: &Path, file: &str) -> NetworkConfig {
+	NetworkConfig::from_file(&dir.join(file)).unwrap()
+}
+
+fn spec_json(config: NetworkConfig, base: &Path) -> serde_json::Value {
+	let spec = from_config(config, base).unwrap();
+	serde_json::from_str(&spec.as_json(false).unwrap()).unwrap()
+}
+
+#[test]
+fn toml_and_json_configs_are_equivalent() {
+	let toml_dir = network_dir("toml", "network.toml", TOML_CONFIG);
+	let json_dir = network_dir("json", "network.json", JSON_CONFIG);
+	let from_toml = read_config(&toml_dir, "network.toml");
+	let from_json = read_config(&json_dir, "network.json");
+
+	assert_eq!(from_toml.name, "Canvas Staging");
+	assert_eq!(from_toml.para_id, 2000);
+	assert_eq!(from_toml.invulnerables.len(), 1);
+	assert_eq!(from_toml.genesis_contracts.len(), 1);
+	assert_eq!(format!("{:?}", from_toml), format!("{:?}", from_json));
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+	let dir = network_dir("unknown", "network.toml", &format!("paraid = 1\n{}", TOML_CONFIG));
+
+	assert!(NetworkConfig::from_file(&dir.join("network.toml")).is_err());
+}
+
+#[test]
+fn spec_is_generated_from_config() {
+	let dir = network_dir("spec", "network.toml", TOML_CONFIG);
+	let json = spec_json(read_config(&dir, "network.toml"), &dir);
+
+	assert_eq!(json["name"], "Canvas Staging");
+	assert_eq!(json["id"], "canvas_staging");
+	assert_eq!(json["para_id"], 2000);
+	assert_eq!(json["properties"]["tokenSymbol"], "CAN");
+
+	let runtime = &json["genesis"]["runtime"];
+	assert_eq!(runtime["parachainInfo"]["parachainId"], 2000);
+	assert_eq!(runtime["collatorSelection"]["invulnerables"], serde_json::json!([ALICE]));
+	assert_eq!(runtime["balances"]["balances"][0][0], ALICE);
+}
+
+#[test]
+fn genesis_contract_paths_are_relative_to_base() {
+	let dir = network_dir("paths", "network.toml", TOML_CONFIG);
+	let json = spec_json(read_config(&dir, "network.toml"), &dir);
+
+	let contract = &json["genesis"]["runtime"]["genesisContracts"]["contracts"][0];
+	assert_eq!(contract["code"], format!("0x{}", HexDisplay::from(&CODE)));
+	assert_eq!(contract["data"], "0x9bae9d5e");
+
+	let elsewhere = network_dir("elsewhere", "network.toml", "");
+	std::fs::remove_file(elsewhere.join("contracts/noop.wasm")).unwrap();
+	assert!(from_config(read_config(&dir, "network.toml"), &elsewhere).is_err());
+}
+
+#[test]
+fn invulnerables_are_required() {
+	let dir = network_dir("invulnerables", "network.toml", TOML_CONFIG);
+	let mut config = read_config(&dir, "network.toml");
+	config.invulnerables.clear();
+
+	assert!(from_config(config, &dir).is_err());
+}
+
+#[test]
+fn endowed_accounts_default_to_none() {
+	let config = TOML_CONFIG.replace(&format!("endowed_accounts = [\"{}\"]\n", ALICE), "");
+	let dir = network_dir("endowed", "network.toml", &config);
+	let json = spec_json(read_config(&dir, "network.toml"), &dir);
+
+	assert_eq!(json["genesis"]["runtime"]["balances"]["balances"], serde_json::json!([]));
+}
+
+#[test]
+fn faucet_is_configured() {
+	let config = format!(
+		"{}\n[faucet]\npot_balance = 1000\nclaim_amount = 10\nclaim_period = 600\n",
+		TOML_CONFIG
+	);
+	let dir = network_dir("faucet", "network.toml", &config);
+	let config = read_config(&dir, "network.toml");
+
+	let faucet = config.faucet.clone().unwrap();
+	assert_eq!((faucet.pot_balance, faucet.claim_amount, faucet.claim_period), (1000, 10, 600));
+
+	#[cfg(feature = "faucet")]
+	assert_eq!(spec_json(config, &dir)["genesis"]["runtime"]["faucet"]["potBalance"], 1000);
+}
+
+#[cfg(feature = "faucet")]
+#[test]
+fn live_rococo_spec_has_no_faucet() {
+	let spec = canvas_node::chain_spec::rococo_testnet_config();
+	let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+
+	assert_eq!(json["genesis"]["runtime"]["faucet"]["potBalance"], 0);
+}
+
+#[test]
+fn relay_properties_match_the_runtime() {
+	let relay = Relay::of_runtime();
+
+	assert_eq!(relay.id(), canvas_runtime::relay::RELAY_CHAIN);
+	assert_eq!(relay.ss58_format(), canvas_runtime::relay::SS58_PREFIX);
+	assert_eq!(canvas_runtime::UNIT, 10u128.pow(relay.token_decimals().into()));
+	assert_ne!(canvas_runtime::relay::network(), xcm::latest::NetworkId::Any);
+}
@@ -0,0 +1,87 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the standalone development node.
+
+use canvas_runtime::{Block, Call};
+use jsonrpsee::{
+	core::client::ClientT,
+	http_client::{HttpClient, HttpClientBuilder},
+	rpc_params,
+};
+use serde_json::Value;
+use sp_runtime::generic::SignedBlock;
+use std::{
+	net::TcpListener,
+	process::{Child, Command},
+	time::{Duration, Instant},
+};
+
+/// Kills the node when the test ends, also when it fails.
+struct Node(Child);
+
+impl Drop for Node {
+	fn drop(&mut self) {
+		let _ = self.0.kill();
+	}
+}
+
+fn free_port() -> u16 {
+	TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+/// Start `canvas --dev --standalone` and wait until its RPC answers.
+async fn start_node() -> (Node, HttpClient) {
+	let rpc_port = free_port();
+	let node = Node(
+		Command::new(env!("CARGO_BIN_EXE_canvas"))
+			.args(["--dev", "--standalone", "--tmp", "--rpc-methods", "unsafe"])
+			.args(["--rpc-port", &rpc_port.to_string(), "--ws-port", &free_port().to_string()])
+			.args(["--port", &free_port().to_string(), "--no-telemetry", "--no-prometheus"])
+			.spawn()
+			.unwrap(),
+	);
+	let client = HttpClientBuilder::default()
+		.build(format!("http://127.0.0.1:{}", rpc_port))
+		.unwrap();
+
+	let started = Instant::now();
+	while client.request::<Value>("system_health", None).await.is_err() {
+		assert!(started.elapsed() < Duration::from_secs(60), "node did not start");
+		tokio::time::sleep(Duration::from_millis(200)).await;
+	}
+
+	(node, client)
+}
+
+#[tokio::test]
+async fn seals_blocks_with_the_mocked_parachain_inherent() {
+	let (_node, client) = start_node().await;
+
+	let created: Value = client
+		.request("engine_createBlock", rpc_params![true, true, Value::Null])
+		.await
+		.unwrap();
+	let block: SignedBlock<Block> =
+		client.request("chain_getBlock", rpc_params![&created["hash"]]).await.unwrap();
+
+	assert_eq!(block.block.header.number, 1);
+	assert!(block
+		.block
+		.extrinsics
+		.iter()
+		.any(|xt| matches!(xt.function, Call::ParachainSystem(_))));
+}
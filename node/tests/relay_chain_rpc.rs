@@ -0,0 +1,80 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for connecting to an external relay chain node over RPC.
+
+use canvas_node::{
+	cli::Cli,
+	service::{build_relay_chain_interface, RelayChain},
+};
+use clap::Parser;
+use cumulus_relay_chain_interface::RelayChainInterface;
+use jsonrpsee::ws_server::{RpcModule, WsServerBuilder, WsServerHandle};
+use polkadot_primitives::v1::Hash as PHash;
+use sc_service::TaskManager;
+
+/// Start a relay chain RPC server which only knows its best block and that it is synced.
+async fn mock_relay_chain(best: PHash) -> (url::Url, WsServerHandle) {
+	let server = WsServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+	let url = format!("ws://{}", server.local_addr().unwrap()).parse().unwrap();
+
+	let mut module = RpcModule::new(());
+	module.register_method("chain_getHead", move |_, _| Ok(best)).unwrap();
+	module.register_method("chain_getBlockHash", move |_, _| Ok(best)).unwrap();
+	module
+		.register_method("system_health", |_, _| {
+			Ok(serde_json::json!({ "peers": 1, "isSyncing": false, "shouldHavePeers": true }))
+		})
+		.unwrap();
+
+	(url, server.start(module).unwrap())
+}
+
+#[tokio::test]
+async fn relay_chain_interface_uses_the_rpc() {
+	let best = PHash::repeat_byte(1);
+	let (url, server) = mock_relay_chain(best).await;
+	let mut task_manager = TaskManager::new(tokio::runtime::Handle::current(), None).unwrap();
+
+	let (relay_chain, collator_key) =
+		build_relay_chain_interface(RelayChain::Rpc(url), None, &mut task_manager)
+			.await
+			.unwrap();
+
+	assert!(collator_key.is_none());
+	assert_eq!(relay_chain.best_block_hash().await.unwrap(), best);
+	assert!(!relay_chain.is_major_syncing().await.unwrap());
+
+	server.stop().unwrap();
+}
+
+#[test]
+fn collators_need_an_embedded_relay_chain() {
+	let url = "ws://127.0.0.1:9944";
+
+	assert!(Cli::try_parse_from(["canvas", "--relay-chain-rpc-url", url]).is_ok());
+	assert!(Cli::try_parse_from(["canvas", "--collator", "--relay-chain-rpc-url", url]).is_err());
+}
+
+#[tokio::test]
+async fn unreachable_relay_chain_rpc_fails() {
+	let mut task_manager = TaskManager::new(tokio::runtime::Handle::current(), None).unwrap();
+	let url = "ws://127.0.0.1:1".parse().unwrap();
+
+	assert!(build_relay_chain_interface(RelayChain::Rpc(url), None, &mut task_manager)
+		.await
+		.is_err());
+}
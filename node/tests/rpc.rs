@@ -0,0 +1,70 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for enabling and disabling groups of RPC modules.
+
+use canvas_node::{
+	cli::Cli,
+	rpc::{DenyUnsafe, RpcGroup},
+	service::CanvasRpcRegistry,
+};
+use clap::Parser;
+use std::collections::HashSet;
+
+fn rpc_groups(args: &[&str]) -> HashSet<RpcGroup> {
+	Cli::parse_from(["canvas"].iter().chain(args)).rpc_groups()
+}
+
+#[test]
+fn default_rpc_groups() {
+	assert_eq!(rpc_groups(&[]), RpcGroup::DEFAULT.into_iter().collect());
+}
+
+#[test]
+fn rpc_groups_are_enabled() {
+	assert_eq!(
+		rpc_groups(&["--enable-rpc", "debugging", "--enable-rpc", "indexer"]),
+		[RpcGroup::CONTRACTS, RpcGroup::DEBUGGING, RpcGroup::INDEXER, RpcGroup::UNSAFE]
+			.into_iter()
+			.collect(),
+	);
+	assert!(rpc_groups(&["--enable-rpc", "downstream"]).contains(&RpcGroup::new("downstream")));
+	assert!(Cli::try_parse_from(["canvas", "--enable-rpc", ""]).is_err());
+}
+
+#[test]
+fn rpc_groups_are_disabled() {
+	assert_eq!(
+		rpc_groups(&["--disable-rpc", "contracts"]),
+		[RpcGroup::UNSAFE].into_iter().collect(),
+	);
+	assert_eq!(
+		rpc_groups(&["--disable-rpc", "unsafe", "--disable-rpc", "contracts"]),
+		HashSet::new(),
+	);
+}
+
+#[test]
+fn unsafe_rpc_group_respects_rpc_methods() {
+	let registry = CanvasRpcRegistry::new([RpcGroup::CONTRACTS, RpcGroup::UNSAFE].into());
+
+	assert!(registry.is_enabled(&RpcGroup::UNSAFE, DenyUnsafe::No));
+	assert!(!registry.is_enabled(&RpcGroup::UNSAFE, DenyUnsafe::Yes));
+	assert!(registry.is_enabled(&RpcGroup::CONTRACTS, DenyUnsafe::Yes));
+
+	let registry = CanvasRpcRegistry::new([RpcGroup::UNSAFE].into());
+	assert!(!registry.is_enabled(&RpcGroup::CONTRACTS, DenyUnsafe::No));
+}
@@ -0,0 +1,240 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Restrict who can upload contract code.
+//!
+//! Meant for deployments where only known accounts may bring new code on-chain. The runtime
+//! has to filter out `Contracts::upload_code` and `Contracts::instantiate_with_code`, and only
+//! let through calls instantiating a code hash, like `Contracts::instantiate`, for code hashes
+//! approved by [`Pallet::is_approved`]. Uploaders then use the calls of this pallet instead,
+//! which check the caller and forward to `pallet-contracts`. Code uploaded that way is
+//! approved right away.
+//!
+//! A privileged origin manages the uploaders and can approve and revoke code hashes. Revoking a
+//! code hash doesn't affect contracts which were already instantiated from it.
+//!
+//! Only dispatched calls can be filtered. Contracts instantiating other contracts with
+//! `seal_instantiate` can use any code which is on-chain, including revoked code. Since only
+//! uploaders can bring code on-chain, this only matters for code which was revoked or put
+//! on-chain in the genesis.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use codec::HasCompact;
+	use frame_support::{pallet_prelude::*, traits::Currency};
+	use frame_system::pallet_prelude::*;
+	use pallet_contracts::{chain_extension::UncheckedFrom, weights::WeightInfo};
+	use sp_runtime::traits::Hash;
+	use sp_std::vec::Vec;
+
+	type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which manages uploaders and approved code hashes.
+		type ManagerOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The accounts which may upload code.
+	#[pallet::storage]
+	pub type Uploaders<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// The code hashes anyone may instantiate.
+	#[pallet::storage]
+	pub type ApprovedCode<T: Config> = StorageMap<_, Identity, T::Hash, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub uploaders: Vec<T::AccountId>,
+		pub approved_code: Vec<T::Hash>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { uploaders: Vec::new(), approved_code: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for uploader in &self.uploaders {
+				Uploaders::<T>::insert(uploader, ());
+			}
+			for code_hash in &self.approved_code {
+				ApprovedCode::<T>::insert(code_hash, ());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account may upload code now.
+		UploaderAdded { account: T::AccountId },
+		/// An account may no longer upload code.
+		UploaderRemoved { account: T::AccountId },
+		/// Anyone may instantiate a code hash now.
+		CodeApproved { code_hash: T::Hash },
+		/// A code hash may no longer be instantiated.
+		CodeRevoked { code_hash: T::Hash },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller may not upload code.
+		NotAnUploader,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		/// Allow `account` to upload code.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn add_uploader(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Uploaders::<T>::insert(&account, ());
+			Self::deposit_event(Event::UploaderAdded { account });
+			Ok(())
+		}
+
+		/// Disallow `account` to upload code.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn remove_uploader(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Uploaders::<T>::remove(&account);
+			Self::deposit_event(Event::UploaderRemoved { account });
+			Ok(())
+		}
+
+		/// Allow anyone to instantiate `code_hash`.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn approve_code(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ApprovedCode::<T>::insert(&code_hash, ());
+			Self::deposit_event(Event::CodeApproved { code_hash });
+			Ok(())
+		}
+
+		/// Disallow instantiating `code_hash`, except through `instantiate_with_code`.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn revoke_code(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ApprovedCode::<T>::remove(&code_hash);
+			Self::deposit_event(Event::CodeRevoked { code_hash });
+			Ok(())
+		}
+
+		/// Upload code as an uploader and approve it.
+		///
+		/// See `pallet_contracts::Pallet::upload_code` for the parameters.
+		#[pallet::weight(
+			<T as pallet_contracts::Config>::WeightInfo::upload_code(code.len() as u32 / 1024)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn upload_code(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<<BalanceOf<T> as HasCompact>::Type>,
+		) -> DispatchResult {
+			Self::ensure_uploader(origin.clone())?;
+			let code_hash = T::Hashing::hash(&code);
+
+			pallet_contracts::Pallet::<T>::upload_code(origin, code, storage_deposit_limit)?;
+
+			Self::approve(code_hash);
+			Ok(())
+		}
+
+		/// Upload code as an uploader, approve it and instantiate a contract from it.
+		///
+		/// See `pallet_contracts::Pallet::instantiate_with_code` for the parameters.
+		#[pallet::weight(
+			<T as pallet_contracts::Config>::WeightInfo::instantiate_with_code(
+				code.len() as u32 / 1024,
+				salt.len() as u32 / 1024,
+			)
+			.saturating_add(*gas_limit)
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn instantiate_with_code(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as HasCompact>::Type>,
+			code: Vec<u8>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_uploader(origin.clone())?;
+			let code_hash = T::Hashing::hash(&code);
+
+			let post_info = pallet_contracts::Pallet::<T>::instantiate_with_code(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+			)?;
+
+			Self::approve(code_hash);
+			Ok(post_info)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether anyone may instantiate `code_hash`.
+		pub fn is_approved(code_hash: &T::Hash) -> bool {
+			ApprovedCode::<T>::contains_key(code_hash)
+		}
+
+		/// Whether `account` may upload code.
+		pub fn is_uploader(account: &T::AccountId) -> bool {
+			Uploaders::<T>::contains_key(account)
+		}
+
+		fn ensure_uploader(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_uploader(&who), Error::<T>::NotAnUploader);
+			Ok(())
+		}
+
+		/// Approve `code_hash` without checking the origin.
+		pub fn approve(code_hash: T::Hash) {
+			if !Self::is_approved(&code_hash) {
+				ApprovedCode::<T>::insert(&code_hash, ());
+				Self::deposit_event(Event::CodeApproved { code_hash });
+			}
+		}
+	}
+}
@@ -0,0 +1,257 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-chain overrides of the contracts [`Schedule`].
+//!
+//! The schedule of `pallet-contracts` is compiled into the runtime, so changing any of its
+//! limits or weights would need a runtime upgrade. This pallet stores overrides for the limits,
+//! the instruction weights and the host function weights which a privileged origin can set.
+//! The runtime reads the schedule through [`Pallet::schedule`], which applies the overrides to
+//! the compiled default. Each part can only be replaced as a whole; setting it to `None` goes
+//! back to the default.
+//!
+//! Limits and instruction weights are applied when contract code is instrumented. Whenever they
+//! change, the instruction weights version is increased so that existing code is
+//! re-instrumented on its next use. Overridden limits must be between one and
+//! `MaxLimitsFactor` times the default, so they can neither make every contract unusable nor
+//! let contracts exhaust the resources of the collators. Likewise, overridden weights must be
+//! between the default divided and multiplied by `MaxLimitsFactor`, and can only be zero where
+//! the default is, so contracts can't get execution for free.
+//!
+//! `pallet-contracts` reads the schedule on every call, but only the first read in a block goes
+//! to the database, later ones are served from the storage cache. This pallet accounts for that
+//! read in `on_initialize`.
+//!
+//! Note that the overrides are kept across runtime upgrades, even if those change the defaults.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, DefaultNoBound, RuntimeDebugNoBound};
+	use frame_system::pallet_prelude::*;
+	use pallet_contracts::{HostFnWeights, InstructionWeights, Limits, Schedule};
+
+	/// The overrides applied to the default schedule.
+	#[derive(
+		Clone, Encode, Decode, PartialEq, Eq, RuntimeDebugNoBound, DefaultNoBound, TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Overrides<T: pallet_contracts::Config> {
+		/// Replaces `Schedule::limits`.
+		pub limits: Option<Limits>,
+		/// Replaces `Schedule::instruction_weights`, apart from the version.
+		pub instruction_weights: Option<InstructionWeights<T>>,
+		/// Replaces `Schedule::host_fn_weights`.
+		pub host_fn_weights: Option<HostFnWeights<T>>,
+		/// Added to the default instruction weights version, increased on every change which
+		/// requires code to be re-instrumented.
+		pub version_offset: u32,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may change the overrides.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// How many times the default an overridden limit or weight can be at most, and how
+		/// many times smaller than the default an overridden weight can be at least.
+		#[pallet::constant]
+		type MaxLimitsFactor: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The overrides applied to the default schedule.
+	#[pallet::storage]
+	#[pallet::getter(fn overrides)]
+	pub type ScheduleOverrides<T: Config> = StorageValue<_, Overrides<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The limits were overridden, or reset if `None`.
+		LimitsSet { limits: Option<Limits> },
+		/// The instruction weights were overridden, or reset if `None`.
+		InstructionWeightsSet { instruction_weights: Option<InstructionWeights<T>> },
+		/// The host function weights were overridden, or reset if `None`.
+		HostFnWeightsSet { host_fn_weights: Option<HostFnWeights<T>> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A limit is zero or above `MaxLimitsFactor` times its default.
+		LimitOutOfBounds,
+		/// A weight is zero or not within `MaxLimitsFactor` times its default.
+		WeightOutOfBounds,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// The first read of the overrides by `pallet-contracts` in this block.
+			T::DbWeight::get().reads(1)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Override the limits of the schedule, or go back to the default with `None`.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_limits(origin: OriginFor<T>, limits: Option<Limits>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(limits) = &limits {
+				ensure!(Self::limits_in_bounds(limits), Error::<T>::LimitOutOfBounds);
+			}
+			ScheduleOverrides::<T>::mutate(|overrides| {
+				overrides.limits = limits.clone();
+				overrides.version_offset = overrides.version_offset.saturating_add(1);
+			});
+			Self::deposit_event(Event::LimitsSet { limits });
+			Ok(())
+		}
+
+		/// Override the instruction weights of the schedule, or go back to the default with
+		/// `None`.
+		///
+		/// The `version` of `instruction_weights` is ignored, it is managed by this pallet.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_instruction_weights(
+			origin: OriginFor<T>,
+			instruction_weights: Option<InstructionWeights<T>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(weights) = &instruction_weights {
+				// Skips the version, which is ignored.
+				ensure!(
+					Self::weights_in_bounds(
+						&weights.encode()[4..],
+						&InstructionWeights::<T>::default().encode()[4..],
+						4,
+					),
+					Error::<T>::WeightOutOfBounds
+				);
+			}
+			ScheduleOverrides::<T>::mutate(|overrides| {
+				overrides.instruction_weights = instruction_weights.clone();
+				overrides.version_offset = overrides.version_offset.saturating_add(1);
+			});
+			Self::deposit_event(Event::InstructionWeightsSet { instruction_weights });
+			Ok(())
+		}
+
+		/// Override the host function weights of the schedule, or go back to the default with
+		/// `None`.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_host_fn_weights(
+			origin: OriginFor<T>,
+			host_fn_weights: Option<HostFnWeights<T>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(weights) = &host_fn_weights {
+				ensure!(
+					Self::weights_in_bounds(
+						&weights.encode(),
+						&HostFnWeights::<T>::default().encode(),
+						8,
+					),
+					Error::<T>::WeightOutOfBounds
+				);
+			}
+			ScheduleOverrides::<T>::mutate(|overrides| {
+				overrides.host_fn_weights = host_fn_weights.clone();
+			});
+			Self::deposit_event(Event::HostFnWeightsSet { host_fn_weights });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The default schedule with the overrides applied.
+		///
+		/// Meant to be used as `pallet_contracts::Config::Schedule`. This costs one storage
+		/// read on every contract call.
+		pub fn schedule() -> Schedule<T> {
+			let overrides = ScheduleOverrides::<T>::get();
+			let mut schedule = Schedule::<T>::default();
+			let version =
+				schedule.instruction_weights.version.saturating_add(overrides.version_offset);
+
+			if let Some(limits) = overrides.limits {
+				schedule.limits = limits;
+			}
+			if let Some(instruction_weights) = overrides.instruction_weights {
+				schedule.instruction_weights = instruction_weights;
+			}
+			if let Some(host_fn_weights) = overrides.host_fn_weights {
+				schedule.host_fn_weights = host_fn_weights;
+			}
+			schedule.instruction_weights.version = version;
+
+			schedule
+		}
+
+		/// Whether every limit is between one and `MaxLimitsFactor` times its default.
+		fn limits_in_bounds(limits: &Limits) -> bool {
+			let default = Limits::default();
+			let factor = T::MaxLimitsFactor::get();
+			[
+				(limits.event_topics, default.event_topics),
+				(limits.globals, default.globals),
+				(limits.parameters, default.parameters),
+				(limits.memory_pages, default.memory_pages),
+				(limits.table_size, default.table_size),
+				(limits.br_table_size, default.br_table_size),
+				(limits.subject_len, default.subject_len),
+				(limits.payload_len, default.payload_len),
+				(limits.code_len, default.code_len),
+			]
+			.iter()
+			.all(|&(limit, default)| limit >= 1 && limit <= default.saturating_mul(factor))
+		}
+
+		/// Whether every weight is between its default divided and multiplied by
+		/// `MaxLimitsFactor`, and only zero if its default is.
+		///
+		/// The weights are given as their encoding. Both weight structs consist of integers of
+		/// the same `width` in bytes only, so this covers every field, including those added
+		/// by future versions of `pallet-contracts`.
+		fn weights_in_bounds(weights: &[u8], default: &[u8], width: usize) -> bool {
+			let factor = u64::from(T::MaxLimitsFactor::get().max(1));
+			let decode = |bytes: &[u8]| {
+				let mut le = [0u8; 8];
+				le[..width].copy_from_slice(bytes);
+				u64::from_le_bytes(le)
+			};
+
+			weights.len() == default.len() &&
+				weights.chunks_exact(width).zip(default.chunks_exact(width)).all(
+					|(weight, default)| {
+						let (weight, default) = (decode(weight), decode(default));
+						weight >= default / factor &&
+							weight <= default.saturating_mul(factor) &&
+							(weight > 0 || default == 0)
+					},
+				)
+		}
+	}
+}
@@ -0,0 +1,140 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks of the contracts scheduler.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{
+	traits::{Currency, Get},
+	weights::Weight,
+};
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::UncheckedFrom;
+use sp_runtime::traits::{Bounded, Hash, Saturating};
+use sp_std::{vec, vec::Vec};
+
+/// A contract which does nothing:
+///
+/// ```wat
+/// (module
+/// 	(import "env" "memory" (memory 1 1))
+/// 	(func (export "deploy"))
+/// 	(func (export "call"))
+/// )
+/// ```
+const NOOP: [u8; 65] = [
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+	0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: `() -> ()`
+	0x02, 0x10, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x01,
+	0x01, 0x01, // import section: `env.memory`
+	0x03, 0x03, 0x02, 0x00, 0x00, // function section: two functions of type 0
+	0x07, 0x11, 0x02, 0x06, 0x64, 0x65, 0x70, 0x6c, 0x6f, 0x79, 0x00, 0x00, 0x04, 0x63, 0x61, 0x6c,
+	0x6c, 0x00, 0x01, // export section: `deploy` and `call`
+	0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, // code section: two empty bodies
+];
+
+/// The maximum length of the input data of a scheduled call used in the benchmarks.
+const MAX_DATA_LEN: u32 = 16 * 1024;
+
+const ID: TaskId = [1u8; 32];
+
+/// Instantiates [`NOOP`] with enough balance to schedule calls.
+fn contract<T: Config>() -> T::AccountId
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+{
+	let caller: T::AccountId = whitelisted_caller();
+	let endowment = BalanceOf::<T>::max_value() / 4u32.into();
+	<T as pallet_contracts::Config>::Currency::make_free_balance_be(
+		&caller,
+		endowment.saturating_mul(2u32.into()),
+	);
+
+	pallet_contracts::Pallet::<T>::instantiate_with_code(
+		RawOrigin::Signed(caller.clone()).into(),
+		endowment,
+		T::BlockWeights::get().max_block,
+		None,
+		NOOP.to_vec(),
+		Vec::new(),
+		Vec::new(),
+	)
+	.expect("The empty contract can be instantiated");
+
+	pallet_contracts::Pallet::<T>::contract_address(&caller, &T::Hashing::hash(&NOOP), &[])
+}
+
+/// Schedules a call of `contract` to itself without input data.
+fn schedule<T: Config>(contract: &T::AccountId, gas_limit: Weight)
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+{
+	Pallet::<T>::schedule(
+		RawOrigin::Signed(contract.clone()).into(),
+		ID,
+		10u32.into(),
+		None,
+		0u32.into(),
+		gas_limit,
+		None,
+		Vec::new(),
+	)
+	.expect("The contract can schedule a call");
+}
+
+benchmarks! {
+	where_clause { where T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]> }
+
+	schedule {
+		let d in 0 .. MAX_DATA_LEN;
+		let contract = contract::<T>();
+		let origin = RawOrigin::Signed(contract.clone());
+	}: _(
+		origin,
+		ID,
+		10u32.into(),
+		None,
+		0u32.into(),
+		T::MaxGasLimit::get(),
+		None,
+		vec![0u8; d as usize]
+	)
+	verify {
+		assert!(Scheduled::<T>::contains_key(&contract, &ID));
+	}
+
+	cancel {
+		let contract = contract::<T>();
+		schedule::<T>(&contract, T::MaxGasLimit::get());
+		let origin = RawOrigin::Signed(contract.clone());
+	}: _(origin, ID)
+	verify {
+		assert!(!Scheduled::<T>::contains_key(&contract, &ID));
+	}
+
+	// Includes a call of the empty contract, which is negligible compared to `Contracts::call`.
+	execute {
+		let contract = contract::<T>();
+		let gas_limit = T::MaxGasLimit::get();
+		schedule::<T>(&contract, gas_limit);
+	}: _(RawOrigin::Root, contract.clone(), ID, 0u32.into(), gas_limit, None, Vec::new())
+	verify {
+		assert!(!Scheduled::<T>::contains_key(&contract, &ID));
+	}
+}
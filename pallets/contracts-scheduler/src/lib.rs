@@ -0,0 +1,364 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Let contracts schedule calls to themselves.
+//!
+//! A contract dispatches [`Call::schedule`] through `seal_call_runtime` to have
+//! `Contracts::call` called on itself after a number of blocks, e.g. to release vested funds or
+//! to bill a subscription. A call can also be repeated a number of times at a fixed period. The
+//! call is executed by `pallet-scheduler` and can be cancelled with [`Call::cancel`] until then.
+//! Only contracts can schedule calls and only to themselves.
+//!
+//! Scheduled calls are executed at the beginning of a block, where nobody pays a transaction
+//! fee for them. The contract therefore pays up front: scheduling reserves a deposit for the
+//! stored call plus the fee for the weight of the call at its `gas_limit`, once per execution.
+//! On each execution the fee for the weight actually used is taken from the reserve and the rest
+//! of that execution's fee is returned, the deposit is returned after the last execution.
+//! Cancelling returns everything still reserved. The `gas_limit` as well as the number of calls
+//! a contract can have scheduled at a time are bounded.
+//!
+//! A contract should cancel its scheduled calls before it terminates, otherwise their reserve
+//! stays with its account. A periodic call shouldn't be cancelled from within its own execution:
+//! its reserve is returned, but the scheduler keeps dispatching the remaining executions, which
+//! fail without calling the contract.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use codec::{Encode, HasCompact};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			schedule::{DispatchTime, Named as ScheduleNamed, LOWEST_PRIORITY},
+			Currency, OnUnbalanced, ReservableCurrency,
+		},
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use pallet_contracts::{chain_extension::UncheckedFrom, weights::WeightInfo as _};
+	use sp_runtime::traits::{AtLeast32BitUnsigned, Convert, Saturating, StaticLookup, Zero};
+	use sp_std::vec::Vec;
+
+	pub(crate) type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	type NegativeImbalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// Identifies a scheduled call among the calls of the same contract.
+	pub type TaskId = [u8; 32];
+
+	/// What is reserved from a contract for one of its scheduled calls.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct Reserve<Balance> {
+		/// The deposit for the stored call, returned after its last execution.
+		pub deposit: Balance,
+		/// The fee for the `gas_limit` of a single execution.
+		pub fee: Balance,
+		/// The number of executions which are still to come.
+		pub remaining: u32,
+	}
+
+	impl<Balance: AtLeast32BitUnsigned + Copy> Reserve<Balance> {
+		/// The amount which is currently reserved.
+		pub fn total(&self) -> Balance {
+			self.deposit.saturating_add(self.fee.saturating_mul(self.remaining.into()))
+		}
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The scheduler which executes the calls.
+		type Scheduler: ScheduleNamed<Self::BlockNumber, Self::ScheduledCall, Self::PalletsOrigin>;
+
+		/// The call type of the runtime, which the scheduler dispatches.
+		type ScheduledCall: From<Call<Self>>;
+
+		/// The caller origin type of the runtime.
+		type PalletsOrigin: From<RawOrigin<Self::AccountId>>;
+
+		/// The deposit reserved for every scheduled call, on top of its fee and of
+		/// `DepositPerByte` for its input data.
+		#[pallet::constant]
+		type ScheduleDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum `gas_limit` of a scheduled call.
+		#[pallet::constant]
+		type MaxGasLimit: Get<Weight>;
+
+		/// The maximum number of calls a contract can have scheduled at a time.
+		#[pallet::constant]
+		type MaxScheduledPerContract: Get<u32>;
+
+		/// Receives the fees paid for executed calls.
+		type OnFeePaid: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: crate::WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The reserve of each call a contract has scheduled.
+	#[pallet::storage]
+	pub type Scheduled<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		TaskId,
+		Reserve<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The number of calls each contract has scheduled.
+	#[pallet::storage]
+	pub type ScheduledCount<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A contract scheduled a call to itself.
+		Scheduled {
+			/// The contract which will be called.
+			contract: T::AccountId,
+			/// The id of the call.
+			id: TaskId,
+			/// The block in which the call will be executed.
+			when: T::BlockNumber,
+		},
+		/// A contract cancelled a scheduled call.
+		Cancelled {
+			/// The contract which would have been called.
+			contract: T::AccountId,
+			/// The id of the call.
+			id: TaskId,
+		},
+		/// A scheduled call was executed.
+		Executed {
+			/// The called contract.
+			contract: T::AccountId,
+			/// The id of the call.
+			id: TaskId,
+			/// The result of the call.
+			result: DispatchResult,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Only contracts can schedule calls.
+		NotAContract,
+		/// The `gas_limit` exceeds `MaxGasLimit`.
+		GasLimitTooHigh,
+		/// The contract has `MaxScheduledPerContract` calls scheduled already.
+		TooManyScheduled,
+		/// The contract already has a call with this id scheduled.
+		AlreadyScheduled,
+		/// The contract has no call with this id scheduled.
+		NotScheduled,
+		/// The scheduler rejected the call.
+		SchedulingFailed,
+		/// The period or the number of executions of a periodic call is zero.
+		InvalidPeriod,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		/// Schedule a call of the calling contract to itself in `after` blocks.
+		///
+		/// With `period` set to `(period, count)`, the call is executed `count` times in total,
+		/// every `period` blocks. The deposit and the fee for `gas_limit` for every execution
+		/// are reserved from the contract until the call is executed or cancelled. See
+		/// `pallet_contracts::Pallet::call` for the other parameters.
+		#[pallet::weight(<T as Config>::WeightInfo::schedule(data.len() as u32))]
+		pub fn schedule(
+			origin: OriginFor<T>,
+			id: TaskId,
+			after: T::BlockNumber,
+			period: Option<(T::BlockNumber, u32)>,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as HasCompact>::Type>,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			let contract = ensure_signed(origin)?;
+			ensure!(Self::is_contract(&contract), Error::<T>::NotAContract);
+			ensure!(gas_limit <= T::MaxGasLimit::get(), Error::<T>::GasLimitTooHigh);
+			ensure!(!Scheduled::<T>::contains_key(&contract, &id), Error::<T>::AlreadyScheduled);
+			let count = ScheduledCount::<T>::get(&contract);
+			ensure!(count < T::MaxScheduledPerContract::get(), Error::<T>::TooManyScheduled);
+			let executions = match period {
+				Some((period, times)) => {
+					ensure!(!period.is_zero() && !times.is_zero(), Error::<T>::InvalidPeriod);
+					times
+				},
+				None => 1,
+			};
+
+			let reserve = Reserve {
+				deposit: T::ScheduleDeposit::get().saturating_add(
+					T::DepositPerByte::get().saturating_mul((data.len() as u32).into()),
+				),
+				fee: T::WeightPrice::convert(Self::call_weight(gas_limit)),
+				remaining: executions,
+			};
+			<T as pallet_contracts::Config>::Currency::reserve(&contract, reserve.total())?;
+
+			let call = Call::<T>::execute {
+				contract: contract.clone(),
+				id,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				data,
+			};
+			T::Scheduler::schedule_named(
+				Self::task_name(&contract, &id),
+				DispatchTime::After(after),
+				period,
+				LOWEST_PRIORITY,
+				RawOrigin::Root.into(),
+				T::ScheduledCall::from(call).into(),
+			)
+			.map_err(|_| Error::<T>::SchedulingFailed)?;
+
+			Scheduled::<T>::insert(&contract, &id, reserve);
+			ScheduledCount::<T>::insert(&contract, count + 1);
+
+			// `DispatchTime::After` counts from the next block.
+			let when = frame_system::Pallet::<T>::block_number()
+				.saturating_add(after)
+				.saturating_add(1u32.into());
+			Self::deposit_event(Event::Scheduled { contract, id, when });
+			Ok(())
+		}
+
+		/// Cancel a call the calling contract scheduled and return its reserve.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel())]
+		pub fn cancel(origin: OriginFor<T>, id: TaskId) -> DispatchResult {
+			let contract = ensure_signed(origin)?;
+			let reserve = Scheduled::<T>::get(&contract, &id).ok_or(Error::<T>::NotScheduled)?;
+
+			T::Scheduler::cancel_named(Self::task_name(&contract, &id))
+				.map_err(|_| Error::<T>::NotScheduled)?;
+			Self::remove(&contract, &id);
+			<T as pallet_contracts::Config>::Currency::unreserve(&contract, reserve.total());
+
+			Self::deposit_event(Event::Cancelled { contract, id });
+			Ok(())
+		}
+
+		/// Execute a scheduled call.
+		///
+		/// Only dispatched by the scheduler. The fee for the used weight is taken from the
+		/// reserve of this execution, the contract gets back the rest of it and, after the last
+		/// execution, the deposit.
+		#[pallet::weight(
+			Pallet::<T>::call_weight(*gas_limit)
+				.saturating_add(<T as Config>::WeightInfo::execute())
+		)]
+		pub fn execute(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			id: TaskId,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as HasCompact>::Type>,
+			data: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let mut reserve =
+				Scheduled::<T>::get(&contract, &id).ok_or(Error::<T>::NotScheduled)?;
+			// Updated before the call, so a cancellation by the contract only returns what is
+			// reserved for the executions still to come.
+			reserve.remaining = reserve.remaining.saturating_sub(1);
+			if reserve.remaining.is_zero() {
+				Self::remove(&contract, &id);
+			} else {
+				Scheduled::<T>::insert(&contract, &id, &reserve);
+			}
+
+			let result = pallet_contracts::Pallet::<T>::call(
+				RawOrigin::Signed(contract.clone()).into(),
+				T::Lookup::unlookup(contract.clone()),
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				data,
+			);
+			let (post_info, result) = match result {
+				Ok(post_info) => (post_info, Ok(())),
+				Err(err) => (err.post_info, Err(err.error)),
+			};
+
+			let weight = post_info.actual_weight.unwrap_or_else(|| Self::call_weight(gas_limit));
+			let fee = T::WeightPrice::convert(weight).min(reserve.fee);
+			let (imbalance, _) =
+				<T as pallet_contracts::Config>::Currency::slash_reserved(&contract, fee);
+			T::OnFeePaid::on_unbalanced(imbalance);
+			let mut refund = reserve.fee.saturating_sub(fee);
+			if reserve.remaining.is_zero() {
+				refund = refund.saturating_add(reserve.deposit);
+			}
+			<T as pallet_contracts::Config>::Currency::unreserve(&contract, refund);
+
+			Self::deposit_event(Event::Executed { contract, id, result });
+			Ok(Some(weight.saturating_add(<T as Config>::WeightInfo::execute())).into())
+		}
+	}
+
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		/// The weight of `Contracts::call` with `gas_limit`.
+		fn call_weight(gas_limit: Weight) -> Weight {
+			<T as pallet_contracts::Config>::WeightInfo::call().saturating_add(gas_limit)
+		}
+
+		/// The name of the call `id` of `contract` in the scheduler.
+		fn task_name(contract: &T::AccountId, id: &TaskId) -> Vec<u8> {
+			(b"contract", contract, id).encode()
+		}
+
+		fn is_contract(account: &T::AccountId) -> bool {
+			pallet_contracts::Pallet::<T>::get_storage(account.clone(), Default::default()).is_ok()
+		}
+
+		fn remove(contract: &T::AccountId, id: &TaskId) {
+			Scheduled::<T>::remove(contract, id);
+			ScheduledCount::<T>::mutate_exists(contract, |count| {
+				*count = count.map(|c| c.saturating_sub(1)).filter(|c| !c.is_zero());
+			});
+		}
+	}
+}
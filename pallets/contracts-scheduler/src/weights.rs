@@ -0,0 +1,77 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_contracts_scheduler`.
+//!
+//! These are estimates, not benchmark results: the storage accesses are counted from the code
+//! and the execution times are conservative guesses. Once the benchmarks in `benchmarking.rs`
+//! were run on the reference hardware with `./scripts/benchmark.sh pallet_contracts_scheduler`,
+//! the runtime should use the generated weights instead of [`EstimatedWeight`].
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_contracts_scheduler`.
+pub trait WeightInfo {
+	fn schedule(d: u32) -> Weight;
+	fn cancel() -> Weight;
+	fn execute() -> Weight;
+}
+
+/// Estimated weights, using the database weights of the runtime.
+pub struct EstimatedWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for EstimatedWeight<T> {
+	/// Reads the contract info and its storage root, writes `Scheduled`, `ScheduledCount`, the
+	/// account of the contract and the lookup and agenda of the scheduler.
+	fn schedule(d: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads_writes(7, 5))
+	}
+
+	/// Writes `Scheduled`, `ScheduledCount`, the account of the contract and the lookup and
+	/// agenda of the scheduler.
+	fn cancel() -> Weight {
+		(50_000_000 as Weight).saturating_add(T::DbWeight::get().reads_writes(5, 5))
+	}
+
+	/// Writes `Scheduled`, `ScheduledCount`, the account of the contract and the account of the
+	/// fee receiver. The call of the contract itself is weighed separately.
+	fn execute() -> Weight {
+		(40_000_000 as Weight).saturating_add(T::DbWeight::get().reads_writes(4, 4))
+	}
+}
+
+/// The same estimates with the database weights of RocksDB, for tests.
+impl WeightInfo for () {
+	fn schedule(d: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads_writes(7, 5))
+	}
+
+	fn cancel() -> Weight {
+		(50_000_000 as Weight).saturating_add(RocksDbWeight::get().reads_writes(5, 5))
+	}
+
+	fn execute() -> Weight {
+		(40_000_000 as Weight).saturating_add(RocksDbWeight::get().reads_writes(4, 4))
+	}
+}
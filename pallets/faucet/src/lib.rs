@@ -0,0 +1,202 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Hand out tokens on local and private networks.
+//!
+//! The faucet has a pot which is funded in the genesis. Anyone can submit [`Call::claim`] as
+//! an unsigned transaction, so that accounts without any balance can get tokens. Each claim
+//! transfers `ClaimAmount` from the pot to the given account. An account can only claim once
+//! every `ClaimPeriod` blocks, the pool rejects claims which come too early or which the pot
+//! can't cover.
+//!
+//! Since claims are free, nothing stops anyone from claiming for many new accounts until the
+//! pot is empty. Don't use this on networks where the tokens have any value.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+/// Error code of a claim which comes too early.
+pub const RATE_LIMITED: u8 = 1;
+
+/// Error code of a claim the pot can't cover.
+pub const POT_EXHAUSTED: u8 = 2;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency handed out.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The id of the pot account.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The priority of claims in the transaction pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The amount handed out per claim.
+	#[pallet::storage]
+	#[pallet::getter(fn claim_amount)]
+	pub type ClaimAmount<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The number of blocks an account has to wait between two claims.
+	#[pallet::storage]
+	#[pallet::getter(fn claim_period)]
+	pub type ClaimPeriod<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The block in which an account claimed last.
+	#[pallet::storage]
+	pub type LastClaim<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The initial balance of the pot.
+		pub pot_balance: BalanceOf<T>,
+		/// The amount handed out per claim.
+		pub claim_amount: BalanceOf<T>,
+		/// The number of blocks an account has to wait between two claims.
+		pub claim_period: T::BlockNumber,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				pot_balance: Zero::zero(),
+				claim_amount: Zero::zero(),
+				claim_period: Zero::zero(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let _ = T::Currency::deposit_creating(&Pallet::<T>::account_id(), self.pot_balance);
+			ClaimAmount::<T>::put(self.claim_amount);
+			ClaimPeriod::<T>::put(self.claim_period);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account claimed tokens.
+		Claimed { account: T::AccountId, amount: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account claimed less than `ClaimPeriod` blocks ago.
+		RateLimited,
+		/// The pot can't cover the claim.
+		PotExhausted,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Transfer `ClaimAmount` from the pot to `dest`.
+		///
+		/// This is an unsigned transaction, see the [`ValidateUnsigned`] implementation.
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 3))]
+		pub fn claim(origin: OriginFor<T>, dest: T::AccountId) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::ensure_can_claim(&dest)?;
+
+			let amount = ClaimAmount::<T>::get();
+			T::Currency::transfer(
+				&Self::account_id(),
+				&dest,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			LastClaim::<T>::insert(&dest, frame_system::Pallet::<T>::block_number());
+
+			Self::deposit_event(Event::Claimed { account: dest, amount });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let dest = match call {
+				Call::claim { dest } => dest,
+				_ => return InvalidTransaction::Call.into(),
+			};
+			Self::ensure_can_claim(dest).map_err(|err| match err {
+				Error::<T>::RateLimited => InvalidTransaction::Custom(RATE_LIMITED),
+				_ => InvalidTransaction::Custom(POT_EXHAUSTED),
+			})?;
+
+			ValidTransaction::with_tag_prefix("Faucet")
+				.priority(T::UnsignedPriority::get())
+				.and_provides(dest)
+				.longevity(64)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account of the pot.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		fn ensure_can_claim(dest: &T::AccountId) -> Result<(), Error<T>> {
+			if let Some(last) = LastClaim::<T>::get(dest) {
+				let now = frame_system::Pallet::<T>::block_number();
+				ensure!(
+					now >= last.saturating_add(ClaimPeriod::<T>::get()),
+					Error::<T>::RateLimited
+				);
+			}
+
+			let pot = T::Currency::free_balance(&Self::account_id());
+			ensure!(
+				pot.saturating_sub(ClaimAmount::<T>::get()) >= T::Currency::minimum_balance(),
+				Error::<T>::PotExhausted
+			);
+			Ok(())
+		}
+	}
+}
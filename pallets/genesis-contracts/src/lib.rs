@@ -0,0 +1,129 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Instantiate contracts in the genesis.
+//!
+//! The genesis config of this pallet lists contracts which are uploaded and instantiated when
+//! the genesis is built, in the order in which they are listed. Their addresses only depend on
+//! the deployer, the code and the salt, so they are known in advance. The deployers have to be
+//! endowed with enough balance for the endowments and the storage deposits.
+//!
+//! Building the genesis fails if any of the contracts can't be instantiated. The code of each
+//! contract is passed to `Config::OnGenesisCode`, e.g. to approve it for further instances.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Notified about the code hash of every contract instantiated in the genesis.
+pub trait OnGenesisCode<Hash> {
+	fn on_genesis_code(code_hash: &Hash);
+}
+
+impl<Hash> OnGenesisCode<Hash> for () {
+	fn on_genesis_code(_code_hash: &Hash) {}
+}
+
+/// A contract to instantiate in the genesis.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase", deny_unknown_fields))]
+pub struct GenesisContract<AccountId, Balance> {
+	/// The account which instantiates the contract.
+	pub deployer: AccountId,
+	/// The Wasm blob of the contract.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub code: Vec<u8>,
+	/// The input of the constructor, i.e. its selector followed by the encoded arguments.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub data: Vec<u8>,
+	/// The balance transferred to the contract.
+	pub endowment: Balance,
+	/// The salt of the contract address.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub salt: Vec<u8>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, sp_runtime::traits::Hash, traits::Currency};
+	use pallet_contracts::chain_extension::UncheckedFrom;
+	use pallet_contracts_primitives::Code;
+
+	type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// Notified about the code of each contract after it was instantiated.
+		type OnGenesisCode: OnGenesisCode<Self::Hash>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The contracts to instantiate, in this order.
+		pub contracts: Vec<GenesisContract<T::AccountId, BalanceOf<T>>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { contracts: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		fn build(&self) {
+			let gas_limit = T::BlockWeights::get().max_block;
+			for contract in &self.contracts {
+				let result = pallet_contracts::Pallet::<T>::bare_instantiate(
+					contract.deployer.clone(),
+					contract.endowment,
+					gas_limit,
+					None,
+					Code::Upload(contract.code.clone().into()),
+					contract.data.clone(),
+					contract.salt.clone(),
+					false,
+				)
+				.result
+				.expect("Genesis contracts must be instantiable");
+				assert!(
+					!result.result.did_revert(),
+					"The constructor of genesis contract {:?} reverted",
+					result.account_id,
+				);
+				T::OnGenesisCode::on_genesis_code(&T::Hashing::hash(&contract.code));
+			}
+		}
+	}
+}
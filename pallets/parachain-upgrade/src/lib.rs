@@ -0,0 +1,86 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Parachain runtime upgrades through an origin other than root.
+//!
+//! `ParachainSystem::authorize_upgrade` can only be dispatched by root. This pallet wraps it and
+//! `ParachainSystem::enact_authorized_upgrade` in calls which are dispatched as root when
+//! [`Config::UpgradeOrigin`] allows it, e.g. for a majority of a council. The upgrade itself
+//! follows the usual path: once authorized, the code with the authorized hash can be enacted
+//! and is applied after the relay chain approved it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::{GetDispatchInfo, UnfilteredDispatchable},
+		pallet_prelude::*,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_std::vec::Vec;
+
+	type ParachainSystemCall<T> = cumulus_pallet_parachain_system::Call<T>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + cumulus_pallet_parachain_system::Config {
+		/// The origin which may authorize and enact runtime upgrades.
+		type UpgradeOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Authorize an upgrade to the code with `code_hash`.
+		///
+		/// Dispatches `ParachainSystem::authorize_upgrade` as root.
+		#[pallet::weight(
+			ParachainSystemCall::<T>::authorize_upgrade { code_hash: *code_hash }
+				.get_dispatch_info()
+				.weight
+		)]
+		pub fn authorize_upgrade(
+			origin: OriginFor<T>,
+			code_hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			T::UpgradeOrigin::ensure_origin(origin)?;
+			ParachainSystemCall::<T>::authorize_upgrade { code_hash }
+				.dispatch_bypass_filter(RawOrigin::Root.into())
+		}
+
+		/// Enact an upgrade to `code`, which must have been authorized before.
+		///
+		/// Dispatches `ParachainSystem::enact_authorized_upgrade` as root.
+		// The weight doesn't depend on the code, which is not copied for it therefore.
+		#[pallet::weight(
+			ParachainSystemCall::<T>::enact_authorized_upgrade { code: Vec::new() }
+				.get_dispatch_info()
+				.weight
+		)]
+		pub fn enact_authorized_upgrade(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::UpgradeOrigin::ensure_origin(origin)?;
+			ParachainSystemCall::<T>::enact_authorized_upgrade { code }
+				.dispatch_bypass_filter(RawOrigin::Root.into())
+		}
+	}
+}
@@ -0,0 +1,250 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Randomness derived from the relay chain.
+//!
+//! Every relay chain block carries the VRF output of its BABE author. The collator reads it
+//! from the relay parent and proves it with a storage proof which is passed to this pallet as
+//! an inherent. The proof is checked against the relay parent storage root which the
+//! `parachain-system` pallet received as part of the validation data of the block.
+//!
+//! Unlike with `pallet-randomness-collective-flip` the collators of this chain can't choose the
+//! seed. They can however withhold the inherent, in which case the previous seed stays in place,
+//! e.g. until a relay chain block with a seed they like comes along. Consumers must therefore
+//! check the block number returned by [`Randomness::random`], which tells how old the seed is,
+//! and reject seeds which are older than they can tolerate.
+//!
+//! Chains which never received a relay chain seed (e.g. a standalone development chain) fall
+//! back to the parent block hash. This is not secure and only meant for testing.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+use codec::{Decode, Encode};
+use frame_support::traits::Randomness;
+use sp_inherents::InherentIdentifier;
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT};
+use sp_std::vec::Vec;
+use sp_trie::StorageProof;
+
+/// The identifier of the inherent carrying the relay chain storage proof.
+pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"relayrnd";
+
+/// The data of the inherent: a storage proof of [`relay_storage_keys`] at the relay parent.
+pub type InherentType = StorageProof;
+
+/// The relay chain storage keys that need to be proven for the inherent.
+///
+/// These are the BABE VRF output of the relay parent author (`Babe::AuthorVrfRandomness`)
+/// and, as a fallback for blocks authored in secondary plain slots which carry no VRF output,
+/// the epoch randomness (`Babe::Randomness`).
+pub fn relay_storage_keys() -> Vec<Vec<u8>> {
+	sp_std::vec![author_vrf_randomness_key(), epoch_randomness_key()]
+}
+
+fn author_vrf_randomness_key() -> Vec<u8> {
+	frame_support::storage::storage_prefix(b"Babe", b"AuthorVrfRandomness").to_vec()
+}
+
+fn epoch_randomness_key() -> Vec<u8> {
+	frame_support::storage::storage_prefix(b"Babe", b"Randomness").to_vec()
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + cumulus_pallet_parachain_system::Config {}
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The latest seed taken from the relay chain.
+	#[pallet::storage]
+	#[pallet::getter(fn relay_randomness)]
+	pub type RelayRandomness<T: Config> = StorageValue<_, [u8; 32], OptionQuery>;
+
+	/// The block in which [`RelayRandomness`] was last updated.
+	#[pallet::storage]
+	#[pallet::getter(fn last_updated)]
+	pub type LastUpdated<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Whether the seed was already updated in the current block.
+	#[pallet::storage]
+	pub(super) type DidUpdate<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The seed can only be updated once per block.
+		AlreadyUpdated,
+		/// The validation data of the current block was not set yet.
+		ValidationDataUnavailable,
+		/// The storage proof doesn't match the relay parent storage root.
+		InvalidProof,
+		/// The relay parent contains no randomness.
+		RandomnessUnavailable,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: T::BlockNumber) -> Weight {
+			// `DidUpdate` is removed in `on_finalize`.
+			T::DbWeight::get().writes(1)
+		}
+
+		fn on_finalize(_: T::BlockNumber) {
+			DidUpdate::<T>::kill();
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Update the seed from a storage proof of the relay parent.
+		///
+		/// This is an inherent and can't be submitted as a transaction.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 3), DispatchClass::Mandatory))]
+		pub fn set_relay_randomness(
+			origin: OriginFor<T>,
+			relay_chain_state: InherentType,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			ensure!(!DidUpdate::<T>::get(), Error::<T>::AlreadyUpdated);
+
+			let randomness = Self::read_relay_randomness(relay_chain_state)?;
+			RelayRandomness::<T>::put(randomness);
+			LastUpdated::<T>::put(frame_system::Pallet::<T>::block_number());
+			DidUpdate::<T>::put(true);
+
+			Ok(())
+		}
+	}
+
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = sp_inherents::MakeFatalError<()>;
+		const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
+
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			let relay_chain_state: InherentType =
+				data.get_data(&INHERENT_IDENTIFIER).ok().flatten()?;
+			Some(Call::set_relay_randomness { relay_chain_state })
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::set_relay_randomness { .. })
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Read the BABE randomness of the relay parent from `relay_chain_state`.
+		fn read_relay_randomness(relay_chain_state: StorageProof) -> Result<[u8; 32], Error<T>> {
+			let root = cumulus_pallet_parachain_system::Pallet::<T>::validation_data()
+				.ok_or(Error::<T>::ValidationDataUnavailable)?
+				.relay_parent_storage_root;
+			let db = relay_chain_state.into_memory_db::<BlakeTwo256>();
+			let read = |key: &[u8]| {
+				sp_trie::read_trie_value::<sp_trie::LayoutV1<BlakeTwo256>, _>(&db, &root, key)
+					.map_err(|_| Error::<T>::InvalidProof)
+			};
+
+			let author_vrf = read(&author_vrf_randomness_key())?
+				.and_then(|raw| Option::<[u8; 32]>::decode(&mut &raw[..]).ok())
+				.flatten();
+			if let Some(randomness) = author_vrf {
+				return Ok(randomness)
+			}
+
+			read(&epoch_randomness_key())?
+				.and_then(|raw| <[u8; 32]>::decode(&mut &raw[..]).ok())
+				.ok_or(Error::<T>::RandomnessUnavailable)
+		}
+	}
+}
+
+impl<T: Config> Randomness<T::Hash, T::BlockNumber> for Pallet<T> {
+	/// Mix `subject` into the latest relay chain seed.
+	///
+	/// The returned block number is the block in which the seed was taken from the relay
+	/// chain.
+	fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		let seed = match RelayRandomness::<T>::get() {
+			Some(randomness) => randomness,
+			None => BlakeTwo256::hash_of(&frame_system::Pallet::<T>::parent_hash()).into(),
+		};
+		let output = (seed, subject).using_encoded(T::Hashing::hash);
+		(output, LastUpdated::<T>::get())
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Access to the randomness provided by this pallet.
+	pub trait RelayRandomnessApi<Hash, BlockNumber> where
+		Hash: codec::Codec,
+		BlockNumber: codec::Codec,
+	{
+		/// The latest seed taken from the relay chain, if any.
+		fn relay_randomness() -> Option<[u8; 32]>;
+
+		/// Randomness for `subject` as seen by contracts through `seal_random`.
+		fn random(subject: Vec<u8>) -> (Hash, BlockNumber);
+	}
+}
+
+/// Provides the storage proof of [`relay_storage_keys`] to the inherent of this pallet.
+///
+/// Providing nothing is fine: the previous seed is kept in that case.
+#[cfg(feature = "std")]
+pub struct InherentDataProvider(Option<InherentType>);
+
+#[cfg(feature = "std")]
+impl InherentDataProvider {
+	/// Create a provider from the storage proof read at the relay parent.
+	pub fn new(relay_chain_state: Option<InherentType>) -> Self {
+		Self(relay_chain_state)
+	}
+}
+
+#[cfg(feature = "std")]
+#[async_trait::async_trait]
+impl sp_inherents::InherentDataProvider for InherentDataProvider {
+	fn provide_inherent_data(
+		&self,
+		inherent_data: &mut sp_inherents::InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		match &self.0 {
+			Some(relay_chain_state) =>
+				inherent_data.put_data(INHERENT_IDENTIFIER, relay_chain_state),
+			None => Ok(()),
+		}
+	}
+
+	async fn try_handle_error(
+		&self,
+		_: &InherentIdentifier,
+		_: &[u8],
+	) -> Option<Result<(), sp_inherents::Error>> {
+		None
+	}
+}
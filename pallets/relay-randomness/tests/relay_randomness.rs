@@ -0,0 +1,278 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the randomness taken from the relay chain.
+
+use codec::Encode;
+use cumulus_primitives_core::{ParaId, PersistedValidationData};
+use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	storage::{storage_prefix, unhashed},
+	traits::{Everything, Hooks, ProvideInherent, Randomness},
+};
+use pallet_relay_randomness::{Call as RelayRandomnessCall, Error, INHERENT_IDENTIFIER};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	DispatchError,
+};
+use sp_trie::StorageProof;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Storage, Event<T>},
+		RelayRandomness: pallet_relay_randomness::{Pallet, Call, Storage, Inherent},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub ParachainId: ParaId = 200.into();
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<u64>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl cumulus_pallet_parachain_system::Config for Test {
+	type Event = Event;
+	type OnSystemEvent = ();
+	type SelfParaId = ParachainId;
+	type DmpMessageHandler = ();
+	type ReservedDmpWeight = ();
+	type OutboundXcmpMessageSource = ();
+	type XcmpMessageHandler = ();
+	type ReservedXcmpWeight = ();
+}
+
+impl pallet_relay_randomness::Config for Test {}
+
+const VRF_OUTPUT: [u8; 32] = [1u8; 32];
+const EPOCH_RANDOMNESS: [u8; 32] = [2u8; 32];
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// A relay chain state with the given BABE randomness and a proof of the randomness keys.
+///
+/// Without `epoch_randomness` the epoch randomness key holds a value which doesn't decode.
+fn relay_chain_state(
+	vrf_output: Option<[u8; 32]>,
+	epoch_randomness: Option<[u8; 32]>,
+) -> (H256, StorageProof) {
+	let mut keys = pallet_relay_randomness::relay_storage_keys().into_iter();
+	let (vrf_key, epoch_key) = (keys.next().unwrap(), keys.next().unwrap());
+
+	let mut builder = RelayStateSproofBuilder { para_id: ParachainId::get(), ..Default::default() };
+	builder.additional_key_values.push((vrf_key, vrf_output.encode()));
+	builder
+		.additional_key_values
+		.push((epoch_key, epoch_randomness.map(|r| r.to_vec()).unwrap_or_else(|| vec![0])));
+	builder.into_state_root_and_proof()
+}
+
+/// Sets the validation data of the current block as `set_validation_data` would.
+fn set_relay_parent_storage_root(relay_parent_storage_root: H256) {
+	let validation_data = PersistedValidationData {
+		relay_parent_number: 1,
+		relay_parent_storage_root,
+		..Default::default()
+	};
+	unhashed::put(&storage_prefix(b"ParachainSystem", b"ValidationData"), &validation_data);
+}
+
+/// Submits `relay_chain_state` as the inherent of the current block.
+fn set_relay_randomness(relay_chain_state: StorageProof) -> Result<(), DispatchError> {
+	RelayRandomness::set_relay_randomness(Origin::none(), relay_chain_state).map_err(Into::into)
+}
+
+#[test]
+fn seed_is_taken_from_vrf_output() {
+	new_test_ext().execute_with(|| {
+		let (root, proof) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+		set_relay_parent_storage_root(root);
+		System::set_block_number(5);
+
+		assert_ok!(set_relay_randomness(proof));
+
+		assert_eq!(RelayRandomness::relay_randomness(), Some(VRF_OUTPUT));
+		assert_eq!(RelayRandomness::last_updated(), 5);
+		assert_eq!(RelayRandomness::random(b"subject").1, 5);
+	});
+}
+
+#[test]
+fn epoch_randomness_is_used_without_vrf_output() {
+	new_test_ext().execute_with(|| {
+		let (root, proof) = relay_chain_state(None, Some(EPOCH_RANDOMNESS));
+		set_relay_parent_storage_root(root);
+
+		assert_ok!(set_relay_randomness(proof));
+
+		assert_eq!(RelayRandomness::relay_randomness(), Some(EPOCH_RANDOMNESS));
+	});
+}
+
+#[test]
+fn proof_must_match_relay_parent_storage_root() {
+	new_test_ext().execute_with(|| {
+		let (_, proof) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+		let (other_root, _) = relay_chain_state(Some([3u8; 32]), Some(EPOCH_RANDOMNESS));
+		set_relay_parent_storage_root(other_root);
+
+		assert_noop!(set_relay_randomness(proof), Error::<Test>::InvalidProof);
+	});
+}
+
+#[test]
+fn proof_must_contain_randomness() {
+	new_test_ext().execute_with(|| {
+		let (root, proof) = relay_chain_state(None, None);
+		set_relay_parent_storage_root(root);
+
+		assert_noop!(set_relay_randomness(proof), Error::<Test>::RandomnessUnavailable);
+	});
+}
+
+#[test]
+fn empty_proof_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let (root, _) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+		set_relay_parent_storage_root(root);
+
+		assert_noop!(set_relay_randomness(StorageProof::empty()), Error::<Test>::InvalidProof);
+	});
+}
+
+#[test]
+fn validation_data_is_required() {
+	new_test_ext().execute_with(|| {
+		let (_, proof) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+
+		assert_noop!(set_relay_randomness(proof), Error::<Test>::ValidationDataUnavailable);
+	});
+}
+
+#[test]
+fn seed_is_updated_once_per_block() {
+	new_test_ext().execute_with(|| {
+		let (root, proof) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+		set_relay_parent_storage_root(root);
+
+		assert_ok!(set_relay_randomness(proof.clone()));
+		assert_noop!(set_relay_randomness(proof.clone()), Error::<Test>::AlreadyUpdated);
+
+		RelayRandomness::on_finalize(1);
+		System::set_block_number(2);
+
+		assert_ok!(set_relay_randomness(proof));
+	});
+}
+
+#[test]
+fn only_none_origin_sets_seed() {
+	new_test_ext().execute_with(|| {
+		let (root, proof) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+		set_relay_parent_storage_root(root);
+
+		assert_noop!(
+			RelayRandomness::set_relay_randomness(Origin::signed(1), proof),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn parent_hash_is_used_without_seed() {
+	new_test_ext().execute_with(|| {
+		System::set_parent_hash(H256::repeat_byte(1));
+		let (first, block_number) = RelayRandomness::random(b"subject");
+		assert_eq!(block_number, 0);
+		assert_ne!(RelayRandomness::random(b"other subject").0, first);
+
+		System::set_parent_hash(H256::repeat_byte(2));
+		assert_ne!(RelayRandomness::random(b"subject").0, first);
+	});
+}
+
+#[test]
+fn seed_does_not_depend_on_parent_hash() {
+	new_test_ext().execute_with(|| {
+		let (root, proof) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+		set_relay_parent_storage_root(root);
+		assert_ok!(set_relay_randomness(proof));
+
+		System::set_parent_hash(H256::repeat_byte(1));
+		let (first, _) = RelayRandomness::random(b"subject");
+		System::set_parent_hash(H256::repeat_byte(2));
+		let (second, _) = RelayRandomness::random(b"subject");
+
+		assert_eq!(first, second);
+		assert_eq!(first, BlakeTwo256::hash_of(&(VRF_OUTPUT, &b"subject"[..])));
+	});
+}
+
+#[test]
+fn inherent_is_created_from_proof() {
+	let (_, proof) = relay_chain_state(Some(VRF_OUTPUT), Some(EPOCH_RANDOMNESS));
+	let mut data = sp_inherents::InherentData::new();
+
+	assert!(RelayRandomness::create_inherent(&data).is_none());
+
+	data.put_data(INHERENT_IDENTIFIER, &proof).unwrap();
+
+	assert!(matches!(
+		RelayRandomness::create_inherent(&data),
+		Some(RelayRandomnessCall::set_relay_randomness { .. })
+	));
+}
@@ -0,0 +1,177 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Call and instantiate contracts from other chains.
+//!
+//! A remote location uses XCM `Transact` with `OriginKind::Xcm` to dispatch one of the calls of
+//! this pallet. The location is converted into the account it controls on this chain (e.g. the
+//! sovereign account of a sibling parachain), which then acts as the caller of the contract.
+//!
+//! Compared to dispatching `Contracts::call` with `OriginKind::SovereignAccount` this keeps the
+//! remote location around, so the events of this pallet tell where a call came from.
+//!
+//! The weight of the calls is the same as of their counterparts in `pallet-contracts`: the
+//! `require_weight_at_most` of the `Transact` has to cover the `gas_limit` plus the base weight
+//! of the call. Fees are paid by buying execution in the XCM message before the `Transact`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use codec::HasCompact;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, EnsureOrigin},
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use pallet_contracts::{chain_extension::UncheckedFrom, weights::WeightInfo};
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::vec::Vec;
+	use xcm::latest::MultiLocation;
+	use xcm_executor::traits::Convert;
+
+	type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin of a `Transact` issued by a remote location.
+		type XcmOrigin: EnsureOrigin<Self::Origin, Success = MultiLocation>;
+
+		/// Converts a remote location into the account it controls on this chain.
+		type LocationToAccountId: Convert<MultiLocation, Self::AccountId>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A remote location called a contract.
+		Called {
+			/// The location the call came from.
+			origin: MultiLocation,
+			/// The account the location controls on this chain.
+			caller: T::AccountId,
+			/// The called contract.
+			contract: T::AccountId,
+		},
+		/// A remote location instantiated a contract.
+		Instantiated {
+			/// The location the call came from.
+			origin: MultiLocation,
+			/// The account the location controls on this chain.
+			deployer: T::AccountId,
+			/// The new contract.
+			contract: T::AccountId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The remote location doesn't control an account on this chain.
+		UnknownLocation,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		/// Call a contract on behalf of a remote location.
+		///
+		/// See `pallet_contracts::Pallet::call` for the parameters.
+		#[pallet::weight(
+			<T as pallet_contracts::Config>::WeightInfo::call().saturating_add(*gas_limit)
+		)]
+		pub fn call(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as HasCompact>::Type>,
+			data: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let (location, caller) = Self::ensure_remote(origin)?;
+			let contract = T::Lookup::lookup(dest.clone())?;
+
+			let post_info = pallet_contracts::Pallet::<T>::call(
+				RawOrigin::Signed(caller.clone()).into(),
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				data,
+			)?;
+
+			Self::deposit_event(Event::Called { origin: location, caller, contract });
+			Ok(post_info)
+		}
+
+		/// Instantiate an already uploaded contract on behalf of a remote location.
+		///
+		/// See `pallet_contracts::Pallet::instantiate` for the parameters.
+		#[pallet::weight(
+			<T as pallet_contracts::Config>::WeightInfo::instantiate(salt.len() as u32 / 1024)
+				.saturating_add(*gas_limit)
+		)]
+		pub fn instantiate(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as HasCompact>::Type>,
+			code_hash: T::Hash,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let (location, deployer) = Self::ensure_remote(origin)?;
+			let contract =
+				pallet_contracts::Pallet::<T>::contract_address(&deployer, &code_hash, &salt);
+
+			let post_info = pallet_contracts::Pallet::<T>::instantiate(
+				RawOrigin::Signed(deployer.clone()).into(),
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code_hash,
+				data,
+				salt,
+			)?;
+
+			Self::deposit_event(Event::Instantiated { origin: location, deployer, contract });
+			Ok(post_info)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Ensure `origin` is a remote location and return it with the account it controls.
+		fn ensure_remote(
+			origin: OriginFor<T>,
+		) -> Result<(MultiLocation, T::AccountId), DispatchError> {
+			let location = T::XcmOrigin::ensure_origin(origin)?;
+			let account = T::LocationToAccountId::convert_ref(&location)
+				.map_err(|()| Error::<T>::UnknownLocation)?;
+			Ok((location, account))
+		}
+	}
+}
@@ -0,0 +1,211 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Access to `pallet_assets` for contracts, e.g. to back a PSP22 token by a native asset.
+//!
+//! All state changing functions are dispatched with the calling contract as the signed origin.
+//! A contract can therefore only move its own assets or assets it was approved to transfer.
+
+use super::ErrorCode;
+use crate::{AccountId, AssetId, Assets, Balance, Call, Origin, Runtime};
+use codec::Encode;
+use frame_support::{
+	dispatch::{DispatchResultWithPostInfo, GetDispatchInfo},
+	traits::{
+		fungibles::{approvals, metadata},
+		Get,
+	},
+};
+use pallet_contracts::chain_extension::{
+	Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_runtime::{traits::Dispatchable, DispatchError};
+
+/// The functions of [`super::ExtensionId::Assets`], identified by the lower half of `func_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+	/// Creates a new asset with the contract as owner.
+	///
+	/// Input: `(AssetId, AccountId, Balance)` as id, admin and minimum balance.
+	Create = 0x0001,
+	/// Mints an amount of an asset to a beneficiary. The contract must be the issuer.
+	///
+	/// Input: `(AssetId, AccountId, Balance)` as id, beneficiary and amount.
+	Mint = 0x0002,
+	/// Transfers an amount of an asset from the contract to a target.
+	///
+	/// Input: `(AssetId, AccountId, Balance)` as id, target and amount.
+	Transfer = 0x0003,
+	/// Approves a delegate to transfer an amount of an asset owned by the contract.
+	///
+	/// Input: `(AssetId, AccountId, Balance)` as id, delegate and amount.
+	Approve = 0x0004,
+	/// Transfers an amount of an asset which the contract was approved to transfer.
+	///
+	/// Input: `(AssetId, AccountId, AccountId, Balance)` as id, owner, destination and amount.
+	TransferApproved = 0x0005,
+	/// Returns the balance of an account.
+	///
+	/// Input: `(AssetId, AccountId)`. Output: `Balance`.
+	BalanceOf = 0x0006,
+	/// Returns the total supply of an asset.
+	///
+	/// Input: `AssetId`. Output: `Balance`.
+	TotalSupply = 0x0007,
+	/// Returns the amount a delegate is allowed to transfer on behalf of an owner.
+	///
+	/// Input: `(AssetId, AccountId, AccountId)` as id, owner and delegate. Output: `Balance`.
+	Allowance = 0x0008,
+	/// Returns the metadata of an asset.
+	///
+	/// Input: `AssetId`. Output: `(Vec<u8>, Vec<u8>, u8)` as name, symbol and decimals.
+	Metadata = 0x0009,
+}
+
+impl TryFrom<u16> for Function {
+	type Error = DispatchError;
+
+	fn try_from(id: u16) -> Result<Self, Self::Error> {
+		match id {
+			0x0001 => Ok(Self::Create),
+			0x0002 => Ok(Self::Mint),
+			0x0003 => Ok(Self::Transfer),
+			0x0004 => Ok(Self::Approve),
+			0x0005 => Ok(Self::TransferApproved),
+			0x0006 => Ok(Self::BalanceOf),
+			0x0007 => Ok(Self::TotalSupply),
+			0x0008 => Ok(Self::Allowance),
+			0x0009 => Ok(Self::Metadata),
+			_ => Err(DispatchError::Other("Unknown chain extension function")),
+		}
+	}
+}
+
+pub(super) fn call<E>(func_id: u16, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+where
+	E: Ext<T = Runtime>,
+	<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	let read_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+	let mut env = env.buf_in_buf_out();
+
+	let call = match Function::try_from(func_id)? {
+		Function::Create => {
+			let (id, admin, min_balance): (AssetId, AccountId, Balance) = env.read_as()?;
+			pallet_assets::Call::create { id, admin: admin.into(), min_balance }
+		},
+		Function::Mint => {
+			let (id, beneficiary, amount): (AssetId, AccountId, Balance) = env.read_as()?;
+			pallet_assets::Call::mint { id, beneficiary: beneficiary.into(), amount }
+		},
+		Function::Transfer => {
+			let (id, target, amount): (AssetId, AccountId, Balance) = env.read_as()?;
+			pallet_assets::Call::transfer { id, target: target.into(), amount }
+		},
+		Function::Approve => {
+			let (id, delegate, amount): (AssetId, AccountId, Balance) = env.read_as()?;
+			pallet_assets::Call::approve_transfer { id, delegate: delegate.into(), amount }
+		},
+		Function::TransferApproved => {
+			let (id, owner, destination, amount): (AssetId, AccountId, AccountId, Balance) =
+				env.read_as()?;
+			pallet_assets::Call::transfer_approved {
+				id,
+				owner: owner.into(),
+				destination: destination.into(),
+				amount,
+			}
+		},
+		Function::BalanceOf => {
+			env.charge_weight(read_weight)?;
+			let (id, who): (AssetId, AccountId) = env.read_as()?;
+			env.write(&Assets::balance(id, who).encode(), false, None)?;
+			return Ok(ErrorCode::Success.into())
+		},
+		Function::TotalSupply => {
+			env.charge_weight(read_weight)?;
+			let id: AssetId = env.read_as()?;
+			env.write(&Assets::total_supply(id).encode(), false, None)?;
+			return Ok(ErrorCode::Success.into())
+		},
+		Function::Allowance => {
+			env.charge_weight(read_weight)?;
+			let (id, owner, delegate): (AssetId, AccountId, AccountId) = env.read_as()?;
+			let allowance =
+				<Assets as approvals::Inspect<AccountId>>::allowance(id, &owner, &delegate);
+			env.write(&allowance.encode(), false, None)?;
+			return Ok(ErrorCode::Success.into())
+		},
+		Function::Metadata => {
+			env.charge_weight(read_weight)?;
+			let id: AssetId = env.read_as()?;
+			let metadata = (
+				<Assets as metadata::Inspect<AccountId>>::name(&id),
+				<Assets as metadata::Inspect<AccountId>>::symbol(&id),
+				<Assets as metadata::Inspect<AccountId>>::decimals(&id),
+			);
+			env.write(&metadata.encode(), false, None)?;
+			return Ok(ErrorCode::Success.into())
+		},
+	};
+
+	let call = Call::Assets(call);
+	let info = call.get_dispatch_info();
+	let charged = env.charge_weight(info.weight)?;
+	let origin = Origin::signed(env.ext().address().clone());
+	let result: DispatchResultWithPostInfo = call.dispatch(origin);
+	let post_info = match &result {
+		Ok(post_info) => *post_info,
+		Err(err) => err.post_info,
+	};
+	env.adjust_weight(charged, post_info.calc_actual_weight(&info));
+
+	match result {
+		Ok(_) => Ok(ErrorCode::Success.into()),
+		Err(err) => error_code(err.error).map(Into::into),
+	}
+}
+
+/// Maps the errors a contract is expected to handle to an [`ErrorCode`].
+///
+/// Any other error traps the contract.
+fn error_code(error: DispatchError) -> Result<ErrorCode, DispatchError> {
+	let is = |other: pallet_assets::Error<Runtime>| error == other.into();
+
+	let code = if is(pallet_assets::Error::BalanceLow) {
+		ErrorCode::AssetsBalanceLow
+	} else if is(pallet_assets::Error::NoPermission) {
+		ErrorCode::AssetsNoPermission
+	} else if is(pallet_assets::Error::Unknown) {
+		ErrorCode::AssetsUnknown
+	} else if is(pallet_assets::Error::Frozen) {
+		ErrorCode::AssetsFrozen
+	} else if is(pallet_assets::Error::InUse) {
+		ErrorCode::AssetsInUse
+	} else if is(pallet_assets::Error::MinBalanceZero) {
+		ErrorCode::AssetsMinBalanceZero
+	} else if is(pallet_assets::Error::Unapproved) {
+		ErrorCode::AssetsUnapproved
+	} else if is(pallet_assets::Error::WouldDie) {
+		ErrorCode::AssetsWouldDie
+	} else if error == pallet_balances::Error::<Runtime>::InsufficientBalance.into() {
+		ErrorCode::InsufficientBalance
+	} else {
+		return Err(error)
+	};
+
+	Ok(code)
+}
@@ -0,0 +1,111 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Chain extensions which give contracts access to runtime functionality.
+//!
+//! The `func_id` a contract passes to `seal_call_chain_extension` is split in two: the upper
+//! 16 bits select one of the [`ExtensionId`]s, the lower 16 bits select a function of that
+//! extension. Input and output buffers are SCALE encoded.
+//!
+//! Errors a contract is expected to handle are reported through the returned status code, see
+//! [`ErrorCode`]. Calling an unknown extension or function traps the contract.
+
+pub mod assets;
+pub mod runtime;
+
+use crate::Runtime;
+use codec::{Decode, Encode};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use scale_info::TypeInfo;
+use sp_runtime::DispatchError;
+
+/// The extensions which are exposed to contracts, identified by the upper half of `func_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionId {
+	/// Read-only access to chain state, see [`runtime::Function`].
+	Runtime = 0,
+	/// Access to `pallet_assets`, see [`assets::Function`].
+	Assets = 1,
+}
+
+impl TryFrom<u16> for ExtensionId {
+	type Error = DispatchError;
+
+	fn try_from(id: u16) -> Result<Self, Self::Error> {
+		match id {
+			0 => Ok(Self::Runtime),
+			1 => Ok(Self::Assets),
+			_ => Err(DispatchError::Other("Unknown chain extension")),
+		}
+	}
+}
+
+/// Status codes returned to a contract by the chain extension.
+///
+/// The values are part of the contract facing API and must never change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[repr(u32)]
+pub enum ErrorCode {
+	/// The function succeeded.
+	Success = 0,
+	/// The relay chain block number is not known in the current context.
+	RelayBlockNumberUnavailable = 1,
+	/// The account balance of the asset is too low.
+	AssetsBalanceLow = 2,
+	/// The contract lacks the permission for this asset operation.
+	AssetsNoPermission = 3,
+	/// The asset does not exist.
+	AssetsUnknown = 4,
+	/// The asset or account is frozen.
+	AssetsFrozen = 5,
+	/// The asset id is already taken.
+	AssetsInUse = 6,
+	/// The minimum balance of an asset must not be zero.
+	AssetsMinBalanceZero = 7,
+	/// No approval exists which would allow the transfer.
+	AssetsUnapproved = 8,
+	/// The source account would be reaped by the operation.
+	AssetsWouldDie = 9,
+	/// The contract cannot pay the deposit for the operation.
+	InsufficientBalance = 10,
+}
+
+impl From<ErrorCode> for RetVal {
+	fn from(code: ErrorCode) -> Self {
+		RetVal::Converging(code as u32)
+	}
+}
+
+/// The chain extension of the Canvas runtime, dispatching to the [`ExtensionId`]s.
+pub struct CanvasChainExtension;
+
+impl ChainExtension<Runtime> for CanvasChainExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let extension_id = (func_id >> 16) as u16;
+		let func_id = (func_id & 0x0000_ffff) as u16;
+
+		match ExtensionId::try_from(extension_id)? {
+			ExtensionId::Runtime => runtime::call(func_id, env),
+			ExtensionId::Assets => assets::call(func_id, env),
+		}
+	}
+}
@@ -0,0 +1,90 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Read-only access to chain state for contracts.
+
+use super::ErrorCode;
+use crate::{AccountId, Balances, ParachainSystem, Runtime, Schedule};
+use codec::Encode;
+use frame_support::traits::Get;
+use pallet_contracts::chain_extension::{
+	Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_runtime::DispatchError;
+
+/// The functions of [`super::ExtensionId::Runtime`], identified by the lower half of `func_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+	/// Returns the number of the relay chain block this parachain block is built on.
+	///
+	/// Output: `u32`.
+	RelayBlockNumber = 0x0001,
+	/// Returns randomness for the subject passed as raw input bytes, exactly like `seal_random`.
+	///
+	/// Output: `(Hash, BlockNumber)`.
+	Random = 0x0002,
+	/// Returns the free balance of another account.
+	///
+	/// Input: `AccountId`. Output: `Balance`.
+	FreeBalance = 0x0003,
+}
+
+impl TryFrom<u16> for Function {
+	type Error = DispatchError;
+
+	fn try_from(id: u16) -> Result<Self, Self::Error> {
+		match id {
+			0x0001 => Ok(Self::RelayBlockNumber),
+			0x0002 => Ok(Self::Random),
+			0x0003 => Ok(Self::FreeBalance),
+			_ => Err(DispatchError::Other("Unknown chain extension function")),
+		}
+	}
+}
+
+pub(super) fn call<E>(func_id: u16, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+where
+	E: Ext<T = Runtime>,
+	<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	let read_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+	let mut env = env.buf_in_buf_out();
+
+	match Function::try_from(func_id)? {
+		Function::RelayBlockNumber => {
+			env.charge_weight(read_weight)?;
+			let validation_data = match ParachainSystem::validation_data() {
+				Some(validation_data) => validation_data,
+				None => return Ok(ErrorCode::RelayBlockNumberUnavailable.into()),
+			};
+			env.write(&validation_data.relay_parent_number.encode(), false, None)?;
+		},
+		Function::Random => {
+			let schedule = Schedule::get();
+			env.charge_weight(schedule.host_fn_weights.random)?;
+			let subject = env.read(schedule.limits.subject_len)?;
+			let random = env.ext().random(&subject);
+			env.write(&random.encode(), false, None)?;
+		},
+		Function::FreeBalance => {
+			env.charge_weight(read_weight)?;
+			let account: AccountId = env.read_as()?;
+			env.write(&Balances::free_balance(&account).encode(), false, None)?;
+		},
+	}
+
+	Ok(ErrorCode::Success.into())
+}
@@ -0,0 +1,158 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementations of traits the pallets of this runtime are configured with.
+
+use crate::{AccountId, Assets, Balances, CollatorSelection, Contracts, Hash, Runtime};
+use frame_support::traits::{
+	fungibles::{Balanced, CreditOf},
+	Currency, Imbalance, OnUnbalanced,
+};
+use pallet_asset_tx_payment::HandleCredit;
+use pallet_contracts::AddressGenerator;
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT};
+use sp_std::prelude::*;
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Deposits imbalances into the pot of the collator selection pallet.
+///
+/// The pot is paid out to the collators as a reward for authoring blocks.
+pub struct ToStakingPot;
+
+impl OnUnbalanced<NegativeImbalance> for ToStakingPot {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		Balances::resolve_creating(&CollatorSelection::account_id(), amount);
+	}
+}
+
+/// Deposits imbalances to the author of the current block, or into the pot of the collator
+/// selection pallet if the author is unknown.
+pub struct ToAuthor;
+
+impl OnUnbalanced<NegativeImbalance> for ToAuthor {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		match pallet_authorship::Pallet::<Runtime>::author() {
+			Some(author) => Balances::resolve_creating(&author, amount),
+			None => ToStakingPot::on_unbalanced(amount),
+		}
+	}
+}
+
+/// Splits transaction fees evenly between the block author and the collator pot, tips go to
+/// the block author entirely.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let (to_author, to_pot) = fees.ration(50, 50);
+			ToStakingPot::on_unbalanced(to_pot);
+			ToAuthor::on_unbalanced(to_author);
+			if let Some(tips) = fees_then_tips.next() {
+				ToAuthor::on_unbalanced(tips);
+			}
+		}
+	}
+}
+
+/// Gives transaction fees paid in assets to the block author, or to the pot of the collator
+/// selection pallet if the author is unknown or can't receive them.
+pub struct AssetsToBlockAuthor;
+
+impl HandleCredit<AccountId, Assets> for AssetsToBlockAuthor {
+	fn handle_credit(credit: CreditOf<AccountId, Assets>) {
+		let credit = match pallet_authorship::Pallet::<Runtime>::author() {
+			Some(author) => match Assets::resolve(&author, credit) {
+				Ok(()) => return,
+				Err(credit) => credit,
+			},
+			None => credit,
+		};
+		// Resolving fails if the credit is below the `min_balance` of an asset the pot doesn't
+		// hold yet. The credit is dropped then, which burns it.
+		let _ = Assets::resolve(&CollatorSelection::account_id(), credit);
+	}
+}
+
+/// Salts starting with this prefix make [`CanvasAddressGenerator`] ignore the deployer.
+pub const UNIVERSAL_SALT_PREFIX: &[u8] = b"canvas:universal:";
+
+/// The salt which deploys a contract with the constructor `input` to a universal address.
+///
+/// It consists of [`UNIVERSAL_SALT_PREFIX`], the hash of `input` and `salt`.
+pub fn universal_salt(input: &[u8], salt: &[u8]) -> Vec<u8> {
+	[UNIVERSAL_SALT_PREFIX, BlakeTwo256::hash(input).as_ref(), salt].concat()
+}
+
+/// Whether `salt` can be used to instantiate a contract with the constructor `input`.
+///
+/// Universal salts must contain the hash of the input, see [`universal_salt`].
+pub fn is_valid_salt(input: &[u8], salt: &[u8]) -> bool {
+	match salt.strip_prefix(UNIVERSAL_SALT_PREFIX) {
+		Some(rest) => rest.starts_with(BlakeTwo256::hash(input).as_ref()),
+		None => true,
+	}
+}
+
+/// Generates contract addresses which can be independent of the deployer.
+///
+/// If the salt starts with [`UNIVERSAL_SALT_PREFIX`] the address only depends on the code hash
+/// and the salt, in the spirit of `CREATE2`. The same contract then ends up at the same address
+/// on every chain using this generator, no matter who deploys it, which allows to compute the
+/// address of a contract before it exists. Other salts give the addresses of
+/// [`pallet_contracts::DefaultAddressGenerator`].
+///
+/// `pallet-contracts` doesn't pass the constructor input to the generator, so a universal salt
+/// has to contain its hash instead, see [`universal_salt`]. The runtime rejects extrinsics whose
+/// universal salt doesn't match their input, see [`crate::UniversalAddressFilter`]. Contracts
+/// instantiating other contracts are not filtered, so they always get the default addresses.
+pub struct CanvasAddressGenerator;
+
+impl AddressGenerator<Runtime> for CanvasAddressGenerator {
+	fn generate_address(deploying_address: &AccountId, code_hash: &Hash, salt: &[u8]) -> AccountId {
+		if !salt.starts_with(UNIVERSAL_SALT_PREFIX) || is_contract(deploying_address) {
+			return pallet_contracts::DefaultAddressGenerator::generate_address(
+				deploying_address,
+				code_hash,
+				salt,
+			)
+		}
+
+		let buf: Vec<u8> = b"canvas_universal_address"
+			.iter()
+			.chain(code_hash.as_ref())
+			.chain(salt)
+			.cloned()
+			.collect();
+		BlakeTwo256::hash(&buf).to_fixed_bytes().into()
+	}
+}
+
+fn is_contract(account: &AccountId) -> bool {
+	Contracts::get_storage(account.clone(), Default::default()).is_ok()
+}
+
+/// Approves the code of the genesis contracts in `pallet-code-permissions`.
+#[cfg(feature = "permissioned-code")]
+pub struct ApproveGenesisCode;
+
+#[cfg(feature = "permissioned-code")]
+impl pallet_genesis_contracts::OnGenesisCode<Hash> for ApproveGenesisCode {
+	fn on_genesis_code(code_hash: &Hash) {
+		crate::CodePermissions::approve(*code_hash);
+	}
+}
@@ -22,15 +22,28 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+pub mod chain_extension;
+pub mod impls;
+pub mod relay;
+pub mod xcm_assets;
+
+use codec::{Decode, Encode, MaxEncodedLen};
 use pallet_contracts::weights::WeightInfo;
 use smallvec::smallvec;
 use sp_api::impl_runtime_apis;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId,
+	u32_trait::{_1, _2, _3, _4},
+	OpaqueMetadata,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto,
+		IdentifyAccount, Verify,
+	},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, MultiSignature, RuntimeDebug,
 };
 
 use sp_std::prelude::*;
@@ -40,7 +53,7 @@ use sp_version::RuntimeVersion;
 
 use frame_support::{
 	construct_runtime, match_type, parameter_types,
-	traits::{EnsureOneOf, Everything},
+	traits::{Contains, EnsureOneOf, EqualPrivilegeOnly, Everything, InstanceFilter, Nothing},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, WEIGHT_PER_SECOND},
 		DispatchClass, IdentityFee, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
@@ -66,12 +79,14 @@ use polkadot_runtime_common::{BlockHashCount, RocksDbWeight, SlowAdjustingFeeUpd
 // XCM Imports
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, CurrencyAdapter,
-	EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset, ParentIsPreset,
+	Account32Hash, AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
+	AsPrefixedGeneralIndex, ConvertedConcreteAssetId, CurrencyAdapter, EnsureXcmOrigin,
+	FixedWeightBounds, FungiblesAdapter, IsConcrete, LocationInverter, NativeAsset, ParentIsPreset,
 	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
-	SignedAccountId32AsNative, SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	UsingComponents,
 };
-use xcm_executor::{Config, XcmExecutor};
+use xcm_executor::{traits::JustTry, Config, XcmExecutor};
 
 /// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
 pub type Signature = MultiSignature;
@@ -83,6 +98,9 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an asset of the assets pallet.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -115,7 +133,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -131,8 +149,35 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	RemoveCollectiveFlip,
 >;
 
+/// Removes the storage of `pallet-randomness-collective-flip` which was replaced by
+/// `pallet-relay-randomness`.
+///
+/// Runs once: chains which upgraded to storage version 1 of `pallet-relay-randomness` (or
+/// started with it) are skipped. Remove this after the next release.
+pub struct RemoveCollectiveFlip;
+
+impl frame_support::traits::OnRuntimeUpgrade for RemoveCollectiveFlip {
+	fn on_runtime_upgrade() -> Weight {
+		use frame_support::{
+			storage::migration,
+			traits::{GetStorageVersion, StorageVersion},
+		};
+
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if RelayRandomness::on_chain_storage_version() >= 1 {
+			return db_weight.reads(1)
+		}
+
+		// `RandomMaterial` is a single value, no matter how many hashes it holds.
+		migration::remove_storage_prefix(b"RandomnessCollectiveFlip", b"RandomMaterial", b"");
+		StorageVersion::new(1).put::<RelayRandomness>();
+		db_weight.reads_writes(1, 2)
+	}
+}
+
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 /// node's balance type.
 ///
@@ -188,10 +233,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("canvas"),
 	impl_name: create_runtime_str!("canvas"),
 	authoring_version: 1,
-	spec_version: 15,
+	spec_version: 16,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	// Since Canvas is a "live" chain (on Rococo anyways), we need to set this to `0` until a
 	// migration path to `state_version = 1` is ready.
 	//
@@ -223,9 +268,9 @@ pub const DAYS: BlockNumber = HOURS * 24;
 pub const CONTRACTS_DEBUG_OUTPUT: bool = true;
 
 // Unit = the base number of indivisible units for balances
-pub const UNIT: Balance = 1_000_000_000_000;
-pub const MILLIUNIT: Balance = 1_000_000_000;
-pub const MICROUNIT: Balance = 1_000_000;
+pub const UNIT: Balance = 10u128.pow(relay::TOKEN_DECIMALS as u32);
+pub const MILLIUNIT: Balance = UNIT / 1_000;
+pub const MICROUNIT: Balance = UNIT / 1_000_000;
 
 /// The existential deposit. Set to 1/10 of the Connected Relay Chain.
 pub const EXISTENTIAL_DEPOSIT: Balance = MILLIUNIT;
@@ -279,7 +324,88 @@ parameter_types! {
 		})
 		.avg_block_initialization(AVERAGE_ON_INITIALIZE_RATIO)
 		.build_or_panic();
-	pub const SS58Prefix: u16 = 42;
+	pub const SS58Prefix: u16 = relay::SS58_PREFIX;
+}
+
+/// The calls which may be dispatched by any origin other than root.
+///
+/// Asset ids from [`xcm_assets::FOREIGN_ASSET_ID_OFFSET`] on are reserved for assets backed by
+/// other chains, so that nobody can occupy them before root registered the asset.
+///
+/// With the `permissioned-code` feature code can only be uploaded through `CodePermissions`
+/// and only approved code hashes can be instantiated, see [`PermissionedCodeFilter`].
+pub struct BaseCallFilter;
+
+impl Contains<Call> for BaseCallFilter {
+	fn contains(call: &Call) -> bool {
+		if matches!(
+			call,
+			Call::Assets(pallet_assets::Call::create { id, .. })
+				if *id >= xcm_assets::FOREIGN_ASSET_ID_OFFSET
+		) {
+			return false
+		}
+
+		if !UniversalAddressFilter::contains(call) {
+			return false
+		}
+
+		#[cfg(feature = "permissioned-code")]
+		if !PermissionedCodeFilter::contains(call) {
+			return false
+		}
+
+		true
+	}
+}
+
+/// Blocks instantiating contracts with a universal salt which doesn't contain the hash of the
+/// constructor input, see [`impls::CanvasAddressGenerator`].
+pub struct UniversalAddressFilter;
+
+impl Contains<Call> for UniversalAddressFilter {
+	fn contains(call: &Call) -> bool {
+		match call {
+			Call::Contracts(
+				pallet_contracts::Call::instantiate { data, salt, .. } |
+				pallet_contracts::Call::instantiate_with_code { data, salt, .. },
+			) |
+			Call::XcmContracts(pallet_xcm_contracts::Call::instantiate { data, salt, .. }) =>
+				impls::is_valid_salt(data, salt),
+			#[cfg(feature = "permissioned-code")]
+			Call::CodePermissions(pallet_code_permissions::Call::instantiate_with_code {
+				data,
+				salt,
+				..
+			}) => impls::is_valid_salt(data, salt),
+			_ => true,
+		}
+	}
+}
+
+/// Blocks uploading code through `pallet-contracts` and instantiating code hashes which
+/// weren't approved by `pallet-code-permissions`, with `Contracts::instantiate` as well as with
+/// `XcmContracts::instantiate`.
+///
+/// Contracts instantiating code with `seal_instantiate` are not affected, see
+/// `pallet_code_permissions`.
+#[cfg(feature = "permissioned-code")]
+pub struct PermissionedCodeFilter;
+
+#[cfg(feature = "permissioned-code")]
+impl Contains<Call> for PermissionedCodeFilter {
+	fn contains(call: &Call) -> bool {
+		match call {
+			Call::Contracts(
+				pallet_contracts::Call::upload_code { .. } |
+				pallet_contracts::Call::instantiate_with_code { .. },
+			) => false,
+			Call::Contracts(pallet_contracts::Call::instantiate { code_hash, .. }) |
+			Call::XcmContracts(pallet_xcm_contracts::Call::instantiate { code_hash, .. }) =>
+				CodePermissions::is_approved(code_hash),
+			_ => true,
+		}
+	}
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -320,9 +446,9 @@ impl frame_system::Config for Runtime {
 	/// The weight of database operations that the runtime can invoke.
 	type DbWeight = RocksDbWeight;
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = BaseCallFilter;
 	/// Weight information for the extrinsics of this pallet.
-	type SystemWeightInfo = ();
+	type SystemWeightInfo = frame_system::weights::SubstrateWeight<Runtime>;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = RuntimeBlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -344,7 +470,7 @@ impl pallet_timestamp::Config for Runtime {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
-	type WeightInfo = (); // TODO: Add benchmarked weights.
+	type WeightInfo = pallet_timestamp::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -370,7 +496,7 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	type DustRemoval = impls::ToStakingPot;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
@@ -385,13 +511,49 @@ parameter_types! {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction =
+		pallet_transaction_payment::CurrencyAdapter<Balances, impls::DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = WeightToFee;
 	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 }
 
+/// Lets transaction fees be paid in sufficient assets instead of the native token.
+///
+/// The fee is converted with the ratio of the asset's `min_balance` to the existential deposit.
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+		pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+		impls::AssetsToBlockAuthor,
+	>;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = deposit(1, 190);
+	pub const ApprovalDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = deposit(1, 68);
+	pub const MetadataDepositPerByte: Balance = deposit(0, 1);
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
@@ -420,35 +582,141 @@ parameter_types! {
 			<Runtime as pallet_contracts::Config>::WeightInfo::on_initialize_per_queue_item(1) -
 			<Runtime as pallet_contracts::Config>::WeightInfo::on_initialize_per_queue_item(0)
 		)) / 5) as u32;
-	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
+	// The default schedule derives the costs of instructions and host functions from
+	// `pallet_contracts::Config::WeightInfo`.
+	// Governance can override parts of it on-chain through `ContractsSchedule`.
+	pub Schedule: pallet_contracts::Schedule<Runtime> = ContractsSchedule::schedule();
+}
+
+/// The calls contracts are allowed to dispatch through `seal_call_runtime`.
+///
+/// Dispatchables exposed to contracts are not allowed to change because that would break already
+/// deployed contracts. Only add calls here which can be kept stable and which are safe to be
+/// dispatched with the contract as signed origin.
+pub struct ContractsCallFilter;
+
+impl Contains<Call> for ContractsCallFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Balances(
+				pallet_balances::Call::transfer { .. } |
+					pallet_balances::Call::transfer_keep_alive { .. }
+			) | Call::Assets(
+				pallet_assets::Call::transfer { .. } |
+					pallet_assets::Call::transfer_keep_alive { .. } |
+					pallet_assets::Call::approve_transfer { .. } |
+					pallet_assets::Call::cancel_approval { .. } |
+					pallet_assets::Call::transfer_approved { .. }
+			) | Call::PolkadotXcm(pallet_xcm::Call::send { .. } | pallet_xcm::Call::execute { .. }) |
+				Call::ContractsScheduler(
+					pallet_contracts_scheduler::Call::schedule { .. } |
+						pallet_contracts_scheduler::Call::cancel { .. }
+				)
+		)
+	}
 }
 
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	/// Randomness derived from the BABE VRF output of the relay chain.
+	type Randomness = RelayRandomness;
 	type Currency = Balances;
 	type Event = Event;
 	type Call = Call;
-	/// The safest default is to allow no calls at all.
+	/// Only a curated set of calls may be dispatched by contracts, see [`ContractsCallFilter`].
 	///
-	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
-	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
-	/// change because that would break already deployed contracts. The `Call` structure itself
-	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = frame_support::traits::Nothing;
+	/// The `Call` structure itself is not allowed to change the indices of existing pallets
+	/// either, since that would break already deployed contracts.
+	type CallFilter = ContractsCallFilter;
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DepositPerByte;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
-	type ChainExtension = ();
+	type ChainExtension = chain_extension::CanvasChainExtension;
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = Schedule;
 	type CallStack = [pallet_contracts::Frame<Self>; 31];
-	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
+	/// Allows deployer independent addresses, see [`impls::CanvasAddressGenerator`].
+	type AddressGenerator = impls::CanvasAddressGenerator;
 }
 
-impl pallet_randomness_collective_flip::Config for Runtime {}
+/// Lets other chains call and instantiate contracts via XCM `Transact` with `OriginKind::Xcm`.
+impl pallet_xcm_contracts::Config for Runtime {
+	type Event = Event;
+	type XcmOrigin = EnsureXcm<RelayOrSiblingLocation>;
+	type LocationToAccountId = ContractCallerToAccountId;
+}
+
+#[cfg(feature = "permissioned-code")]
+impl pallet_code_permissions::Config for Runtime {
+	type Event = Event;
+	type ManagerOrigin = GovernanceOrigin;
+}
+
+parameter_types! {
+	pub const MaxScheduleLimitsFactor: u32 = 4;
+}
+
+impl pallet_contracts_schedule::Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = GovernanceOrigin;
+	type MaxLimitsFactor = MaxScheduleLimitsFactor;
+}
+
+/// Instantiates the contracts listed in the chain spec when the genesis is built.
+impl pallet_genesis_contracts::Config for Runtime {
+	/// Genesis code can be instantiated by anyone, like code uploaded by an uploader.
+	#[cfg(feature = "permissioned-code")]
+	type OnGenesisCode = impls::ApproveGenesisCode;
+	#[cfg(not(feature = "permissioned-code"))]
+	type OnGenesisCode = ();
+}
+
+parameter_types! {
+	// The entry of this pallet and the one in the scheduler agenda.
+	pub const ScheduleDeposit: Balance = deposit(2, 0);
+	pub MaxScheduledGasLimit: Weight = Perbill::from_percent(10) *
+		RuntimeBlockWeights::get().max_block;
+	pub const MaxScheduledPerContract: u32 = 16;
+}
+
+/// Lets contracts schedule calls to themselves through [`ContractsCallFilter`].
+impl pallet_contracts_scheduler::Config for Runtime {
+	type Event = Event;
+	type Scheduler = Scheduler;
+	type ScheduledCall = Call;
+	type PalletsOrigin = OriginCaller;
+	type ScheduleDeposit = ScheduleDeposit;
+	type MaxGasLimit = MaxScheduledGasLimit;
+	type MaxScheduledPerContract = MaxScheduledPerContract;
+	type OnFeePaid = impls::ToStakingPot;
+	type WeightInfo = pallet_contracts_scheduler::weights::EstimatedWeight<Runtime>;
+}
+
+impl pallet_relay_randomness::Config for Runtime {}
+
+#[cfg(feature = "sudo")]
+impl pallet_sudo::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+}
+
+#[cfg(feature = "faucet")]
+parameter_types! {
+	pub const FaucetPalletId: PalletId = PalletId(*b"canvfcet");
+	// Claims are free, so they shouldn't push out paying transactions.
+	pub const FaucetUnsignedPriority: TransactionPriority = 0;
+}
+
+#[cfg(feature = "faucet")]
+impl pallet_faucet::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = FaucetPalletId;
+	type UnsignedPriority = FaucetUnsignedPriority;
+}
 
 impl parachain_info::Config for Runtime {}
 
@@ -456,7 +724,7 @@ impl cumulus_pallet_aura_ext::Config for Runtime {}
 
 parameter_types! {
 	pub const RelayLocation: MultiLocation = MultiLocation::parent();
-	pub const RelayNetwork: NetworkId = NetworkId::Any;
+	pub RelayNetwork: NetworkId = relay::network();
 	pub RelayChainOrigin: Origin = cumulus_pallet_xcm::Origin::Relay.into();
 	pub Ancestry: MultiLocation = Parachain(ParachainInfo::parachain_id().into()).into();
 }
@@ -473,7 +741,29 @@ pub type LocationToAccountId = (
 	AccountId32Aliases<RelayNetwork, AccountId>,
 );
 
-/// Means for transacting assets on this chain.
+// The remote locations which may call and instantiate contracts: the relay chain, sibling
+// parachains and the accounts on them.
+match_type! {
+	pub type RelayOrSiblingLocation: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: Here } |
+		MultiLocation { parents: 1, interior: X1(Junction::AccountId32 { .. }) } |
+		MultiLocation { parents: 1, interior: X1(Parachain(_)) } |
+		MultiLocation { parents: 1, interior: X2(Parachain(_), Junction::AccountId32 { .. }) } |
+		MultiLocation { parents: 1, interior: X2(Parachain(_), AccountKey20 { .. }) }
+	};
+}
+
+/// Converts the remote locations which call contracts into the accounts they act through.
+///
+/// The relay chain and sibling parachains use their sovereign accounts. Accounts on them get an
+/// account derived from the hash of their location, which nobody on this chain has the key for.
+pub type ContractCallerToAccountId = (
+	ParentIsPreset<AccountId>,
+	SiblingParachainConvertsVia<Sibling, AccountId>,
+	Account32Hash<RelayNetwork, AccountId>,
+);
+
+/// Means for transacting the relay chain token on this chain.
 pub type LocalAssetTransactor = CurrencyAdapter<
 	// Use this currency:
 	Balances,
@@ -487,6 +777,55 @@ pub type LocalAssetTransactor = CurrencyAdapter<
 	(),
 >;
 
+parameter_types! {
+	/// The chain which is the reserve of the assets in [`ReserveAssetsTransactor`].
+	pub StatemintLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(1000)));
+	/// The assets pallet of [`StatemintLocation`], assets are identified by a `GeneralIndex` in it.
+	pub StatemintAssetsPalletLocation: MultiLocation =
+		MultiLocation::new(1, X2(Parachain(1000), PalletInstance(50)));
+	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+	/// Receives the XCM fees paid in reserve-backed assets.
+	pub AssetsFeeReceiver: AccountId = PotId::get().into_account();
+}
+
+/// Matches reserve-backed assets of [`StatemintAssetsPalletLocation`] to local asset ids.
+pub type ReserveAssetsMatcher = ConvertedConcreteAssetId<
+	AssetId,
+	Balance,
+	AsPrefixedGeneralIndex<StatemintAssetsPalletLocation, AssetId, xcm_assets::ForeignAssetId>,
+	JustTry,
+>;
+
+/// Means for transacting reserve-backed assets, which are stored in `pallet-assets`.
+pub type ReserveAssetsTransactor = FungiblesAdapter<
+	// Use this fungibles implementation:
+	Assets,
+	// Use this matcher to find the local asset id:
+	ReserveAssetsMatcher,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// We don't track any teleports of assets.
+	Nothing,
+	// The account used for teleport checking (unused since we don't track teleports):
+	CheckingAccount,
+>;
+
+/// Means for transacting assets on this chain.
+pub type AssetTransactors = (LocalAssetTransactor, ReserveAssetsTransactor);
+
+/// Chains which are trusted as reserves of the assets they send.
+///
+/// Each chain is only trusted for the assets located on it.
+pub type Reserves = (NativeAsset, xcm_assets::ReserveAssetsFrom<StatemintLocation>);
+
+/// Buys execution with the relay chain token or, failing that, with a reserve-backed asset.
+pub type Traders = (
+	UsingComponents<IdentityFee<Balance>, RelayLocation, AccountId, Balances, ()>,
+	xcm_assets::AssetsTrader<IdentityFee<Balance>, ReserveAssetsMatcher, AssetsFeeReceiver>,
+);
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -533,14 +872,14 @@ impl Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
 	// How to withdraw and deposit an asset.
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
-	type IsReserve = NativeAsset;
+	type IsReserve = Reserves;
 	type IsTeleporter = NativeAsset; // Should be enough to allow teleportation of ROC
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
-	type Trader = UsingComponents<IdentityFee<Balance>, RelayLocation, AccountId, Balances, ()>;
+	type Trader = Traders;
 	type ResponseHandler = PolkadotXcm;
 	type AssetTrap = PolkadotXcm;
 	type AssetClaims = PolkadotXcm;
@@ -551,8 +890,37 @@ parameter_types! {
 	pub const MaxDownwardMessageWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 10;
 }
 
-/// No local origins on this chain are allowed to dispatch XCM sends/executions.
-pub type LocalOriginToLocation = ();
+/// Converts a local origin into the location XCM messages are sent or executed from.
+///
+/// Signed origins become an `AccountId32` junction of the same account. Contracts dispatch calls
+/// with a signed origin of their own account, so messages sent by a contract originate from the
+/// contract's account just like those of a regular user.
+pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, RelayNetwork>;
+
+/// The messages local origins may execute with `PolkadotXcm::execute`.
+///
+/// Teleports are rejected because no other chain trusts this one to teleport assets. Reserve
+/// transfers are rejected for the same reason as in `XcmReserveTransferFilter`. `Transact` is
+/// rejected because it would let contracts dispatch calls which [`ContractsCallFilter`] doesn't
+/// allow.
+pub struct LocalXcmExecuteFilter;
+
+impl Contains<(MultiLocation, Xcm<Call>)> for LocalXcmExecuteFilter {
+	fn contains((_, message): &(MultiLocation, Xcm<Call>)) -> bool {
+		fn is_allowed(message: &Xcm<Call>) -> bool {
+			message.0.iter().all(|instruction| match instruction {
+				InitiateTeleport { .. } |
+				ReceiveTeleportedAsset(..) |
+				TransferReserveAsset { .. } |
+				DepositReserveAsset { .. } |
+				Transact { .. } => false,
+				SetErrorHandler(xcm) | SetAppendix(xcm) => is_allowed(xcm),
+				_ => true,
+			})
+		}
+		is_allowed(message)
+	}
+}
 
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
@@ -568,10 +936,12 @@ impl pallet_xcm::Config for Runtime {
 	type SendXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
 	type XcmRouter = XcmRouter;
 	type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
-	type XcmExecuteFilter = Everything;
+	type XcmExecuteFilter = LocalXcmExecuteFilter;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
-	type XcmTeleportFilter = Everything;
-	type XcmReserveTransferFilter = Everything;
+	type XcmTeleportFilter = Nothing;
+	// Canvas isn't the reserve of any asset it holds, so the destination would reject the
+	// deposited assets while they stay locked in its sovereign account here.
+	type XcmReserveTransferFilter = Nothing;
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Origin = Origin;
@@ -593,10 +963,7 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type ChannelInfo = ParachainSystem;
 	type VersionWrapper = ();
 	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
-	type ControllerOrigin = EnsureOneOf<
-		EnsureRoot<AccountId>,
-		EnsureXcm<IsMajorityOfBody<RelayLocation, ExecutiveBody>>,
-	>;
+	type ControllerOrigin = GovernanceOrigin;
 	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
 }
 
@@ -623,7 +990,7 @@ impl pallet_session::Config for Runtime {
 	// Essentially just Aura, but lets be pedantic.
 	type SessionHandler = <SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = SessionKeys;
-	type WeightInfo = (); // TODO: Add benchmarked weights.
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_aura::Config for Runtime {
@@ -641,9 +1008,9 @@ parameter_types! {
 	pub const ExecutiveBody: BodyId = BodyId::Executive;
 }
 
-// We allow root and the Relay Chain council to execute privileged collator selection operations.
-pub type CollatorSelectionUpdateOrigin =
-	EnsureOneOf<EnsureRoot<AccountId>, EnsureXcm<IsMajorityOfBody<RelayLocation, ExecutiveBody>>>;
+// We allow root, the local council and the Relay Chain council to execute privileged collator
+// selection operations.
+pub type CollatorSelectionUpdateOrigin = GovernanceOrigin;
 
 impl pallet_collator_selection::Config for Runtime {
 	type Event = Event;
@@ -658,46 +1025,409 @@ impl pallet_collator_selection::Config for Runtime {
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
-	type WeightInfo = (); // TODO: Add benchmarked weights.
+	type WeightInfo = pallet_collator_selection::weights::SubstrateWeight<Runtime>;
 }
 
-// Create the runtime by composing the FRAME pallets that were previously configured.
-construct_runtime!(
-	pub enum Runtime where
-		Block = Block,
-		NodeBlock = opaque::Block,
-		UncheckedExtrinsic = UncheckedExtrinsic,
-	{
-		// System support stuff.
-		System: frame_system::{Pallet, Call, Config, Storage, Event<T>} = 0,
-		ParachainSystem: cumulus_pallet_parachain_system::{
-			Pallet, Call, Config, Storage, Inherent, Event<T>, ValidateUnsigned,
-		} = 1,
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage} = 2,
-		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent} = 3,
-		ParachainInfo: parachain_info::{Pallet, Storage, Config} = 4,
-
-		// Monetary stuff.
-		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
-		TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 11,
-
-		// Collator support. The order of these 4 are important and shall not change.
-		Authorship: pallet_authorship::{Pallet, Call, Storage} = 20,
-		CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>, Config<T>} = 21,
-		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 22,
-		Aura: pallet_aura::{Pallet, Storage, Config<T>} = 23,
-		AuraExt: cumulus_pallet_aura_ext::{Pallet, Storage, Config} = 24,
-
-		// XCM helpers.
-		XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 30,
-		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin, Config} = 31,
-		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
-		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 33,
-
-		// Smart Contracts.
-		Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>} = 40,
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		RuntimeBlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = ();
+	type NoPreimagePostponement = ();
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+pub type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
+}
+
+pub type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = TechnicalMotionDuration;
+	type MaxProposals = TechnicalMaxProposals;
+	type MaxMembers = TechnicalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const InstantAllowed: bool = true;
+	pub const MinimumDeposit: Balance = 100 * UNIT;
+	pub const EnactmentPeriod: BlockNumber = DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const PreimageByteDeposit: Balance = deposit(0, 1);
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
+}
+
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type VoteLockingPeriod = EnactmentPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A straight majority of the council can decide what their next motion is.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	/// A super-majority can have the next scheduled referendum be a straight majority-carries
+	/// vote.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	/// Two thirds of the technical committee can have an external proposal tabled immediately
+	/// with a shorter voting period, e.g. for a runtime upgrade.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	// To cancel a proposal before it has been passed, the technical committee must be unanimous
+	// or Root must agree.
+	type CancelProposalOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	// Any single technical committee member may veto a coming council proposal, however they
+	// can only do it once and it lasts only for the cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = impls::ToStakingPot;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
+}
+
+/// Root (i.e. a referendum), a majority of the local council or a majority of the Relay Chain
+/// executive body.
+///
+/// Runtime upgrades are authorized through `ParachainUpgrade`, which dispatches
+/// `ParachainSystem::authorize_upgrade` as root for this origin.
+pub type GovernanceOrigin = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	EnsureOneOf<
+		pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+		EnsureXcm<IsMajorityOfBody<RelayLocation, ExecutiveBody>>,
+	>,
+>;
+
+/// Lets [`GovernanceOrigin`] authorize and enact runtime upgrades without a referendum.
+impl pallet_parachain_upgrade::Config for Runtime {
+	type UpgradeOrigin = GovernanceOrigin;
+}
+
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+	pub const DepositBase: Balance = deposit(1, 88);
+	// Additional storage item size of 32 bytes.
+	pub const DepositFactor: Balance = deposit(0, 32);
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = DepositBase;
+	type DepositFactor = DepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 40);
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u16 = 32;
+	// One storage item; key size 32, value size 16.
+	pub const AnnouncementDepositBase: Balance = deposit(1, 48);
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
+	pub const MaxPending: u16 = 32;
+}
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Allows all calls.
+	Any,
+	/// Allows all calls which can't move funds of the proxied account.
+	NonTransfer,
+	/// Allows governance calls.
+	Governance,
+	/// Allows rejecting announcements of other proxies.
+	CancelProxy,
+	/// Allows calls to `pallet-contracts` only, e.g. for a hot key that deploys and calls
+	/// contracts. Note that those calls can still transfer value to contracts.
+	Contracts,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
 	}
-);
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer =>
+				matches!(
+					c,
+					Call::System(..) |
+						Call::Scheduler(..) |
+						Call::Timestamp(..) |
+						Call::Authorship(..) |
+						Call::CollatorSelection(..) |
+						Call::Session(..) | Call::Council(..) |
+						Call::TechnicalCommittee(..) |
+						Call::Democracy(..) |
+						Call::Utility(..) | Call::Multisig(..) |
+						Call::Proxy(..)
+				),
+			ProxyType::Governance => matches!(
+				c,
+				Call::Council(..) |
+					Call::TechnicalCommittee(..) |
+					Call::Democracy(..) |
+					Call::Utility(..)
+			),
+			ProxyType::CancelProxy => matches!(
+				c,
+				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) |
+					Call::Utility(..) |
+					Call::Multisig(..)
+			),
+			// `Utility` is allowed so that contract calls can be batched, the calls in a batch
+			// are checked by this filter as well.
+			ProxyType::Contracts => matches!(c, Call::Contracts(..) | Call::Utility(..)),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, ProxyType::Governance | ProxyType::CancelProxy) => true,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+/// Creates the runtime from `pallets`, which need to be enclosed in braces.
+///
+/// `construct_runtime!` doesn't support `#[cfg]` attributes on pallets, so pallets behind a
+/// cargo feature are appended to the list by a chain of macros which ends here.
+macro_rules! construct_canvas_runtime {
+	({ $($pallets:tt)* }) => {
+		construct_runtime!(
+			pub enum Runtime where
+				Block = Block,
+				NodeBlock = opaque::Block,
+				UncheckedExtrinsic = UncheckedExtrinsic,
+			{
+				$($pallets)*
+			}
+		);
+	};
+}
+
+/// Appends `pallet-sudo` to `pallets` if the `sudo` feature is enabled.
+#[cfg(feature = "sudo")]
+macro_rules! with_sudo {
+	({ $($pallets:tt)* }) => {
+		construct_canvas_runtime!({
+			$($pallets)*
+
+			// Sudo, only meant for test networks.
+			Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 255,
+		});
+	};
+}
+
+#[cfg(not(feature = "sudo"))]
+macro_rules! with_sudo {
+	({ $($pallets:tt)* }) => {
+		construct_canvas_runtime!({ $($pallets)* });
+	};
+}
+
+/// Appends `pallet-code-permissions` to `pallets` if the `permissioned-code` feature is enabled.
+#[cfg(feature = "permissioned-code")]
+macro_rules! with_code_permissions {
+	({ $($pallets:tt)* }) => {
+		with_sudo!({
+			$($pallets)*
+
+			// Restricts who can upload contract code.
+			CodePermissions: pallet_code_permissions::{Pallet, Call, Config<T>, Storage, Event<T>} = 44,
+		});
+	};
+}
+
+#[cfg(not(feature = "permissioned-code"))]
+macro_rules! with_code_permissions {
+	({ $($pallets:tt)* }) => {
+		with_sudo!({ $($pallets)* });
+	};
+}
+
+/// Appends `pallet-faucet` to `pallets` if the `faucet` feature is enabled.
+#[cfg(feature = "faucet")]
+macro_rules! with_faucet {
+	({ $($pallets:tt)* }) => {
+		with_code_permissions!({
+			$($pallets)*
+
+			// Faucet, only meant for local and private networks.
+			Faucet: pallet_faucet::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned} = 254,
+		});
+	};
+}
+
+#[cfg(not(feature = "faucet"))]
+macro_rules! with_faucet {
+	({ $($pallets:tt)* }) => {
+		with_code_permissions!({ $($pallets)* });
+	};
+}
+
+// Create the runtime by composing the FRAME pallets that were previously configured.
+with_faucet!({
+	// System support stuff.
+	System: frame_system::{Pallet, Call, Config, Storage, Event<T>} = 0,
+	ParachainSystem: cumulus_pallet_parachain_system::{
+		Pallet, Call, Config, Storage, Inherent, Event<T>, ValidateUnsigned,
+	} = 1,
+	Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent} = 3,
+	ParachainInfo: parachain_info::{Pallet, Storage, Config} = 4,
+	RelayRandomness: pallet_relay_randomness::{Pallet, Call, Storage, Inherent} = 5,
+	Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>} = 6,
+
+	// Monetary stuff.
+	Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
+	TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 11,
+	Assets: pallet_assets::{Pallet, Call, Storage, Event<T>} = 12,
+	AssetTxPayment: pallet_asset_tx_payment::{Pallet} = 13,
+
+	// Collator support. The order of these 4 are important and shall not change.
+	Authorship: pallet_authorship::{Pallet, Call, Storage} = 20,
+	CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>, Config<T>} = 21,
+	Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 22,
+	Aura: pallet_aura::{Pallet, Storage, Config<T>} = 23,
+	AuraExt: cumulus_pallet_aura_ext::{Pallet, Storage, Config} = 24,
+
+	// XCM helpers.
+	XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 30,
+	PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin, Config} = 31,
+	CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
+	DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 33,
+
+	// Smart Contracts.
+	Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>} = 40,
+	XcmContracts: pallet_xcm_contracts::{Pallet, Call, Event<T>} = 41,
+	ContractsScheduler: pallet_contracts_scheduler::{Pallet, Call, Storage, Event<T>} = 42,
+	ContractsSchedule: pallet_contracts_schedule::{Pallet, Call, Storage, Event<T>} = 43,
+	GenesisContracts: pallet_genesis_contracts::{Pallet, Config<T>} = 45,
+
+	// Governance.
+	Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 50,
+	TechnicalCommittee: pallet_collective::<Instance2>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 51,
+	Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>} = 52,
+	ParachainUpgrade: pallet_parachain_upgrade::{Pallet, Call} = 53,
+
+	// Account management.
+	Utility: pallet_utility::{Pallet, Call, Event} = 60,
+	Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 61,
+	Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 62,
+});
 
 impl_runtime_apis! {
 	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
@@ -850,6 +1580,16 @@ impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, B
 		}
 	}
 
+	impl pallet_relay_randomness::RelayRandomnessApi<Block, Hash, BlockNumber> for Runtime {
+		fn relay_randomness() -> Option<[u8; 32]> {
+			RelayRandomness::relay_randomness()
+		}
+
+		fn random(subject: Vec<u8>) -> (Hash, BlockNumber) {
+			<RelayRandomness as frame_support::traits::Randomness<Hash, BlockNumber>>::random(&subject)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade() -> (Weight, Weight) {
@@ -878,8 +1618,18 @@ impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, B
 
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_balances, Balances);
+			list_benchmark!(list, extra, pallet_assets, Assets);
+			list_benchmark!(list, extra, pallet_session, SessionBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_collator_selection, CollatorSelection);
+			list_benchmark!(list, extra, pallet_contracts, Contracts);
+			list_benchmark!(list, extra, pallet_contracts_scheduler, ContractsScheduler);
+			list_benchmark!(list, extra, pallet_scheduler, Scheduler);
+			list_benchmark!(list, extra, pallet_collective, Council);
+			list_benchmark!(list, extra, pallet_democracy, Democracy);
+			list_benchmark!(list, extra, pallet_utility, Utility);
+			list_benchmark!(list, extra, pallet_multisig, Multisig);
+			list_benchmark!(list, extra, pallet_proxy, Proxy);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -915,9 +1665,18 @@ impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, B
 
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
+			add_benchmark!(params, batches, pallet_assets, Assets);
 			add_benchmark!(params, batches, pallet_session, SessionBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_collator_selection, CollatorSelection);
+			add_benchmark!(params, batches, pallet_contracts, Contracts);
+			add_benchmark!(params, batches, pallet_contracts_scheduler, ContractsScheduler);
+			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
+			add_benchmark!(params, batches, pallet_collective, Council);
+			add_benchmark!(params, batches, pallet_democracy, Democracy);
+			add_benchmark!(params, batches, pallet_utility, Utility);
+			add_benchmark!(params, batches, pallet_multisig, Multisig);
+			add_benchmark!(params, batches, pallet_proxy, Proxy);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
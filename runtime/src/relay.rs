@@ -0,0 +1,119 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Constants of the relay chains the runtime can be built for.
+//!
+//! Rococo is the default, the `westend`, `kusama` and `polkadot` features target the other relay
+//! chains. They determine the native token, the SS58 prefix of addresses and the XCM network of
+//! local accounts. The constants of the targeted relay chain are re-exported at the top of this
+//! module, the ones of every relay chain are in its own module, e.g. for the chain specs of the
+//! node.
+
+use xcm::latest::NetworkId;
+
+#[cfg(any(
+	all(feature = "westend", feature = "kusama"),
+	all(feature = "westend", feature = "polkadot"),
+	all(feature = "kusama", feature = "polkadot"),
+))]
+compile_error!("Only one of the `westend`, `kusama` and `polkadot` features can be enabled");
+
+#[cfg(feature = "kusama")]
+pub use kusama::*;
+#[cfg(feature = "polkadot")]
+pub use polkadot::*;
+#[cfg(not(any(feature = "westend", feature = "kusama", feature = "polkadot")))]
+pub use rococo::*;
+#[cfg(feature = "westend")]
+pub use westend::*;
+
+/// The Rococo test network.
+pub mod rococo {
+	use super::NetworkId;
+
+	/// The id of the relay chain.
+	pub const RELAY_CHAIN: &str = "rococo";
+	/// The symbol of the native token, which is the one of the relay chain.
+	pub const TOKEN_SYMBOL: &str = "ROC";
+	/// The decimals of the native token.
+	pub const TOKEN_DECIMALS: u8 = 12;
+	/// The SS58 prefix of addresses.
+	pub const SS58_PREFIX: u16 = 42;
+
+	/// The XCM network of accounts on the relay chain and on this chain.
+	///
+	/// Rococo doesn't have a network id of its own, its XCM config uses the one of Polkadot.
+	pub fn network() -> NetworkId {
+		NetworkId::Polkadot
+	}
+}
+
+/// The Westend test network.
+pub mod westend {
+	use super::NetworkId;
+
+	/// The id of the relay chain.
+	pub const RELAY_CHAIN: &str = "westend";
+	/// The symbol of the native token, which is the one of the relay chain.
+	pub const TOKEN_SYMBOL: &str = "WND";
+	/// The decimals of the native token.
+	pub const TOKEN_DECIMALS: u8 = 12;
+	/// The SS58 prefix of addresses.
+	pub const SS58_PREFIX: u16 = 42;
+
+	/// The XCM network of accounts on the relay chain and on this chain.
+	pub fn network() -> NetworkId {
+		NetworkId::Named(b"Westend".to_vec())
+	}
+}
+
+/// The Kusama network.
+pub mod kusama {
+	use super::NetworkId;
+
+	/// The id of the relay chain.
+	pub const RELAY_CHAIN: &str = "kusama";
+	/// The symbol of the native token, which is the one of the relay chain.
+	pub const TOKEN_SYMBOL: &str = "KSM";
+	/// The decimals of the native token.
+	pub const TOKEN_DECIMALS: u8 = 12;
+	/// The SS58 prefix of addresses.
+	pub const SS58_PREFIX: u16 = 2;
+
+	/// The XCM network of accounts on the relay chain and on this chain.
+	pub fn network() -> NetworkId {
+		NetworkId::Kusama
+	}
+}
+
+/// The Polkadot network.
+pub mod polkadot {
+	use super::NetworkId;
+
+	/// The id of the relay chain.
+	pub const RELAY_CHAIN: &str = "polkadot";
+	/// The symbol of the native token, which is the one of the relay chain.
+	pub const TOKEN_SYMBOL: &str = "DOT";
+	/// The decimals of the native token.
+	pub const TOKEN_DECIMALS: u8 = 10;
+	/// The SS58 prefix of addresses.
+	pub const SS58_PREFIX: u16 = 0;
+
+	/// The XCM network of accounts on the relay chain and on this chain.
+	pub fn network() -> NetworkId {
+		NetworkId::Polkadot
+	}
+}
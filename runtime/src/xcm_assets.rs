@@ -0,0 +1,175 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Support for assets which are reserve-backed by another chain.
+//!
+//! Such assets live in `pallet-assets` under an id derived from their location on the reserve
+//! chain. They need to be created by root as *sufficient* assets before they can be received,
+//! their `min_balance` also determines how many of them buy the same weight as the native
+//! token.
+
+use crate::{AccountId, AssetId, Assets, Balance, Balances, Runtime};
+use frame_support::{
+	traits::{fungibles::Mutate, tokens::BalanceConversion, Get},
+	weights::{Weight, WeightToFeePolynomial},
+};
+use pallet_assets::BalanceToAssetBalance;
+use sp_runtime::traits::ConvertInto;
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::{Convert, FilterAssetLocation, MatchesFungibles, WeightTrader};
+
+/// Local ids of reserve-backed assets start here, lower ids are free for local assets.
+///
+/// Creating an asset with a higher id is reserved to root, see [`crate::BaseCallFilter`].
+pub const FOREIGN_ASSET_ID_OFFSET: AssetId = 1 << 31;
+
+/// Converts the `GeneralIndex` of an asset on its reserve chain into the local asset id.
+pub struct ForeignAssetId;
+
+impl Convert<u128, AssetId> for ForeignAssetId {
+	fn convert(id: u128) -> Result<AssetId, u128> {
+		AssetId::try_from(id)
+			.ok()
+			.and_then(|local| local.checked_add(FOREIGN_ASSET_ID_OFFSET))
+			.ok_or(id)
+	}
+
+	fn reverse(id: AssetId) -> Result<u128, AssetId> {
+		id.checked_sub(FOREIGN_ASSET_ID_OFFSET).map(Into::into).ok_or(id)
+	}
+}
+
+/// Accepts assets as reserve-backed if they are sent by `Reserve` and are located on it.
+pub struct ReserveAssetsFrom<Reserve>(PhantomData<Reserve>);
+
+impl<Reserve: Get<MultiLocation>> FilterAssetLocation for ReserveAssetsFrom<Reserve> {
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		let reserve = Reserve::get();
+		let location = match &asset.id {
+			Concrete(location) => location,
+			Abstract(_) => return false,
+		};
+
+		origin == &reserve &&
+			location.parents == reserve.parents &&
+			location.interior.len() >= reserve.interior.len() &&
+			reserve.interior.iter().zip(location.interior.iter()).all(|(a, b)| a == b)
+	}
+}
+
+/// Buys weight with reserve-backed assets.
+///
+/// The price is the native fee as given by `WeightToFee`, converted with the ratio of the
+/// `min_balance` of the asset to the existential deposit. Fees are minted to `Receiver` once
+/// the message was executed.
+pub struct AssetsTrader<WeightToFee, Matcher, Receiver> {
+	weight: Weight,
+	/// The asset used to pay and the amount of it which was taken.
+	paid: Option<(MultiLocation, AssetId, Balance)>,
+	_phantom: PhantomData<(WeightToFee, Matcher, Receiver)>,
+}
+
+impl<WeightToFee, Matcher, Receiver> WeightTrader for AssetsTrader<WeightToFee, Matcher, Receiver>
+where
+	WeightToFee: WeightToFeePolynomial<Balance = Balance>,
+	Matcher: MatchesFungibles<AssetId, Balance>,
+	Receiver: Get<AccountId>,
+{
+	fn new() -> Self {
+		Self { weight: 0, paid: None, _phantom: PhantomData }
+	}
+
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: xcm_executor::Assets,
+	) -> Result<xcm_executor::Assets, XcmError> {
+		let fee = WeightToFee::calc(&weight);
+
+		for asset in payment.fungible_assets_iter() {
+			let (location, asset_id) = match (&asset.id, Matcher::matches_fungibles(&asset)) {
+				(Concrete(location), Ok((asset_id, _))) => (location.clone(), asset_id),
+				_ => continue,
+			};
+			// Only one asset can be used to pay for a message.
+			if matches!(&self.paid, Some((_, paid_with, _)) if *paid_with != asset_id) {
+				continue
+			}
+			let amount = match to_asset_balance(fee, asset_id) {
+				Some(amount) => amount,
+				None => continue,
+			};
+
+			let unused = payment
+				.checked_sub((location.clone(), amount).into())
+				.map_err(|_| XcmError::TooExpensive)?;
+			let paid = self.paid.as_ref().map_or(0, |(_, _, paid)| *paid);
+			self.weight = self.weight.saturating_add(weight);
+			self.paid = Some((location, asset_id, paid.saturating_add(amount)));
+			return Ok(unused)
+		}
+
+		Err(XcmError::TooExpensive)
+	}
+
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		let (location, asset_id, paid) = self.paid.as_mut()?;
+		let weight = weight.min(self.weight);
+		let amount = to_asset_balance(WeightToFee::calc(&weight), *asset_id)?.min(*paid);
+
+		self.weight -= weight;
+		*paid -= amount;
+		if amount > 0 {
+			Some((location.clone(), amount).into())
+		} else {
+			None
+		}
+	}
+}
+
+impl<WeightToFee, Matcher, Receiver: Get<AccountId>> Drop
+	for AssetsTrader<WeightToFee, Matcher, Receiver>
+{
+	fn drop(&mut self) {
+		if let Some((_, asset_id, paid)) = self.paid {
+			if paid > 0 {
+				// The fee was withdrawn from the payer when it was put into holding. Minting fails
+				// e.g. if the fee is below the `min_balance` of an asset the receiver doesn't hold
+				// yet, the fee is burned then.
+				let receiver = Receiver::get();
+				if let Err(err) =
+					<Assets as Mutate<AccountId>>::mint_into(asset_id, &receiver, paid)
+				{
+					log::warn!(
+						target: "runtime::xcm",
+						"Failed to pay a fee of {} of asset {} to {:?}: {:?}",
+						paid,
+						asset_id,
+						receiver,
+						err,
+					);
+				}
+			}
+		}
+	}
+}
+
+/// Convert a native `balance` into an amount of a sufficient asset.
+fn to_asset_balance(balance: Balance, asset_id: AssetId) -> Option<Balance> {
+	BalanceToAssetBalance::<Balances, Runtime, ConvertInto>::to_asset_balance(balance, asset_id)
+		.ok()
+}
@@ -0,0 +1,110 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for paying transaction fees with assets.
+
+mod common;
+
+use canvas_runtime::{
+	impls::AssetsToBlockAuthor, Assets, Balances, BalancesConfig, Call, CollatorSelection,
+	GenesisConfig, Origin, Runtime, UNIT,
+};
+use common::{ALICE, BOB};
+use frame_support::{
+	assert_ok,
+	traits::fungibles::{Balanced, Inspect},
+	weights::GetDispatchInfo,
+};
+use pallet_asset_tx_payment::{ChargeAssetTxPayment, HandleCredit};
+use sp_runtime::traits::SignedExtension;
+
+const SUFFICIENT: u32 = 1;
+const INSUFFICIENT: u32 = 2;
+const MIN_BALANCE: u128 = 1_000;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = common::new_test_ext_with(GenesisConfig {
+		balances: BalancesConfig { balances: vec![(ALICE, 1_000 * UNIT), (BOB, UNIT)] },
+		..Default::default()
+	});
+	ext.execute_with(|| {
+		for (id, is_sufficient) in [(SUFFICIENT, true), (INSUFFICIENT, false)] {
+			assert_ok!(Assets::force_create(
+				Origin::root(),
+				id,
+				ALICE.into(),
+				is_sufficient,
+				MIN_BALANCE
+			));
+			assert_ok!(Assets::mint(Origin::signed(ALICE), id, BOB.into(), 1_000_000 * UNIT));
+		}
+	});
+	ext
+}
+
+fn charge(asset: u32) -> Result<(), ()> {
+	let call = Call::Balances(pallet_balances::Call::transfer { dest: ALICE.into(), value: 0 });
+	ChargeAssetTxPayment::<Runtime>::from(0, Some(asset))
+		.pre_dispatch(&BOB, &call, &call.get_dispatch_info(), 100)
+		.map(|_| ())
+		.map_err(|_| ())
+}
+
+#[test]
+fn fees_are_paid_with_sufficient_asset() {
+	new_test_ext().execute_with(|| {
+		let before = Assets::balance(SUFFICIENT, &BOB);
+
+		assert_eq!(charge(SUFFICIENT), Ok(()));
+
+		assert!(Assets::balance(SUFFICIENT, &BOB) < before);
+		assert_eq!(Balances::free_balance(&BOB), UNIT);
+	});
+}
+
+#[test]
+fn insufficient_asset_cannot_pay_fees() {
+	new_test_ext().execute_with(|| {
+		let before = Assets::balance(INSUFFICIENT, &BOB);
+
+		assert_eq!(charge(INSUFFICIENT), Err(()));
+
+		assert_eq!(Assets::balance(INSUFFICIENT, &BOB), before);
+	});
+}
+
+#[test]
+fn fees_go_to_pot_without_author() {
+	new_test_ext().execute_with(|| {
+		let pot = CollatorSelection::account_id();
+
+		AssetsToBlockAuthor::handle_credit(Assets::issue(SUFFICIENT, MIN_BALANCE));
+
+		assert_eq!(Assets::balance(SUFFICIENT, &pot), MIN_BALANCE);
+	});
+}
+
+#[test]
+fn fees_below_min_balance_are_burned() {
+	new_test_ext().execute_with(|| {
+		let total_issuance = <Assets as Inspect<_>>::total_issuance(SUFFICIENT);
+
+		AssetsToBlockAuthor::handle_credit(Assets::issue(SUFFICIENT, MIN_BALANCE - 1));
+
+		assert_eq!(Assets::balance(SUFFICIENT, &CollatorSelection::account_id()), 0);
+		assert_eq!(<Assets as Inspect<_>>::total_issuance(SUFFICIENT), total_issuance);
+	});
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the assets chain extension.
+
+mod common;
+
+use canvas_runtime::{
+	chain_extension::{assets::Function, ErrorCode},
+	AccountId, AssetId, Assets, Balance, Call, Contracts, Origin, UNIT,
+};
+use codec::{Decode, Encode};
+use common::{call_chain_extension, new_test_ext, ALICE, BOB, CHAIN_EXTENSION_PROXY, GAS_LIMIT};
+use frame_support::weights::GetDispatchInfo;
+
+const ASSET: AssetId = 1;
+
+const CREATE: u32 = 0x0001_0001;
+const MINT: u32 = 0x0001_0002;
+const TRANSFER: u32 = 0x0001_0003;
+const APPROVE: u32 = 0x0001_0004;
+const BALANCE_OF: u32 = 0x0001_0006;
+const TOTAL_SUPPLY: u32 = 0x0001_0007;
+const ALLOWANCE: u32 = 0x0001_0008;
+
+fn proxy() -> AccountId {
+	common::instantiate_with(ALICE, CHAIN_EXTENSION_PROXY, 100 * UNIT, Vec::new(), Vec::new())
+		.unwrap()
+}
+
+/// Calls `func_id` and returns its status code.
+fn status(proxy: &AccountId, func_id: u32, input: impl Encode) -> u32 {
+	call_chain_extension(ALICE, proxy.clone(), func_id, input.encode()).unwrap().0
+}
+
+/// Calls the read-only `func_id` and decodes its output.
+fn read<T: Decode>(proxy: &AccountId, func_id: u32, input: impl Encode) -> T {
+	let (status, output) =
+		call_chain_extension(ALICE, proxy.clone(), func_id, input.encode()).unwrap();
+	assert_eq!(status, ErrorCode::Success as u32);
+	T::decode(&mut &output[..]).unwrap()
+}
+
+#[test]
+fn functions_are_identified_by_the_lower_half() {
+	assert_eq!(Function::try_from(0x0001u16), Ok(Function::Create));
+	assert_eq!(Function::try_from(0x0005u16), Ok(Function::TransferApproved));
+	assert_eq!(Function::try_from(0x0009u16), Ok(Function::Metadata));
+	assert!(Function::try_from(0x0000u16).is_err());
+	assert!(Function::try_from(0x000au16).is_err());
+}
+
+#[test]
+fn contract_manages_its_own_asset() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+
+		assert_eq!(status(&proxy, CREATE, (ASSET, proxy.clone(), 1 as Balance)), 0);
+
+		assert_eq!(status(&proxy, MINT, (ASSET, proxy.clone(), 100 as Balance)), 0);
+		assert_eq!(status(&proxy, TRANSFER, (ASSET, BOB, 30 as Balance)), 0);
+		assert_eq!(status(&proxy, APPROVE, (ASSET, BOB, 20 as Balance)), 0);
+
+		assert_eq!(read::<Balance>(&proxy, BALANCE_OF, (ASSET, BOB)), 30);
+		assert_eq!(read::<Balance>(&proxy, BALANCE_OF, (ASSET, proxy.clone())), 70);
+		assert_eq!(read::<Balance>(&proxy, TOTAL_SUPPLY, ASSET), 100);
+		assert_eq!(read::<Balance>(&proxy, ALLOWANCE, (ASSET, proxy.clone(), BOB)), 20);
+	});
+}
+
+#[test]
+fn expected_errors_are_returned_as_status() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+		assert!(Assets::create(Origin::signed(ALICE), ASSET, ALICE.into(), 1).is_ok());
+
+		assert_eq!(
+			status(&proxy, CREATE, (ASSET, proxy.clone(), 1 as Balance)),
+			ErrorCode::AssetsInUse as u32
+		);
+		assert_eq!(
+			status(&proxy, CREATE, (ASSET + 1, proxy.clone(), 0 as Balance)),
+			ErrorCode::AssetsMinBalanceZero as u32
+		);
+		assert_eq!(
+			status(&proxy, MINT, (ASSET, proxy.clone(), 100 as Balance)),
+			ErrorCode::AssetsNoPermission as u32
+		);
+		assert_eq!(
+			status(&proxy, TRANSFER, (ASSET + 2, BOB, 1 as Balance)),
+			ErrorCode::AssetsUnknown as u32
+		);
+		assert_eq!(
+			status(&proxy, TRANSFER, (ASSET, BOB, 1 as Balance)),
+			ErrorCode::AssetsBalanceLow as u32
+		);
+	});
+}
+
+#[test]
+fn malformed_input_traps() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+
+		assert!(call_chain_extension(ALICE, proxy, CREATE, ASSET.encode()).is_err());
+	});
+}
+
+#[test]
+fn dispatch_weight_is_charged() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+		let input = (ASSET, proxy.clone(), 1 as Balance);
+		let data = CREATE.to_le_bytes().into_iter().chain(input.encode()).collect();
+		let weight = Call::Assets(pallet_assets::Call::create {
+			id: ASSET,
+			admin: proxy.clone().into(),
+			min_balance: 1,
+		})
+		.get_dispatch_info()
+		.weight;
+
+		let result = Contracts::bare_call(ALICE, proxy, 0, GAS_LIMIT, None, data, false);
+
+		assert!(result.result.is_ok());
+		assert!(result.gas_consumed >= weight);
+	});
+}
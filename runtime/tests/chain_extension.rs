@@ -0,0 +1,105 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the dispatch of the chain extension and the runtime functions.
+
+mod common;
+
+use canvas_runtime::{
+	chain_extension::{runtime::Function, ErrorCode, ExtensionId},
+	Balance, Balances, Contracts, Runtime, UNIT,
+};
+use codec::{Decode, Encode};
+use common::{call_chain_extension, new_test_ext, ALICE, BOB, CHAIN_EXTENSION_PROXY, GAS_LIMIT};
+use frame_support::traits::Get;
+use pallet_contracts::chain_extension::RetVal;
+
+fn proxy() -> canvas_runtime::AccountId {
+	common::instantiate_with(ALICE, CHAIN_EXTENSION_PROXY, 100 * UNIT, Vec::new(), Vec::new())
+		.unwrap()
+}
+
+#[test]
+fn func_id_is_split_into_extension_and_function() {
+	assert_eq!(ExtensionId::try_from(0u16), Ok(ExtensionId::Runtime));
+	assert_eq!(ExtensionId::try_from(1u16), Ok(ExtensionId::Assets));
+	assert!(ExtensionId::try_from(2u16).is_err());
+
+	assert_eq!(Function::try_from(0x0001u16), Ok(Function::RelayBlockNumber));
+	assert_eq!(Function::try_from(0x0002u16), Ok(Function::Random));
+	assert_eq!(Function::try_from(0x0003u16), Ok(Function::FreeBalance));
+	assert!(Function::try_from(0x0000u16).is_err());
+}
+
+#[test]
+fn error_codes_are_returned_as_status() {
+	assert!(matches!(RetVal::from(ErrorCode::Success), RetVal::Converging(0)));
+	assert!(matches!(RetVal::from(ErrorCode::RelayBlockNumberUnavailable), RetVal::Converging(1)));
+	assert!(matches!(RetVal::from(ErrorCode::AssetsBalanceLow), RetVal::Converging(2)));
+	assert!(matches!(RetVal::from(ErrorCode::InsufficientBalance), RetVal::Converging(10)));
+}
+
+#[test]
+fn free_balance_of_another_account() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+
+		let (status, output) =
+			call_chain_extension(ALICE, proxy, 0x0000_0003, BOB.encode()).unwrap();
+
+		assert_eq!(status, ErrorCode::Success as u32);
+		assert_eq!(Balance::decode(&mut &output[..]).unwrap(), Balances::free_balance(&BOB));
+	});
+}
+
+#[test]
+fn relay_block_number_is_unavailable_outside_of_a_parachain_block() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+
+		let (status, _) = call_chain_extension(ALICE, proxy, 0x0000_0001, Vec::new()).unwrap();
+
+		assert_eq!(status, ErrorCode::RelayBlockNumberUnavailable as u32);
+	});
+}
+
+#[test]
+fn unknown_extension_or_function_traps() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+
+		assert!(call_chain_extension(ALICE, proxy.clone(), 0x0002_0003, BOB.encode()).is_err());
+		assert!(call_chain_extension(ALICE, proxy.clone(), 0x0000_0004, BOB.encode()).is_err());
+		// The upper half selects the extension: this is not `FreeBalance`.
+		assert!(call_chain_extension(ALICE, proxy, 0x0001_0003, BOB.encode()).is_err());
+	});
+}
+
+#[test]
+fn reads_are_charged() {
+	new_test_ext().execute_with(|| {
+		let proxy = proxy();
+		let gas_consumed = |func_id: u32, input: Vec<u8>| {
+			let data = func_id.to_le_bytes().into_iter().chain(input).collect();
+			Contracts::bare_call(ALICE, proxy.clone(), 0, GAS_LIMIT, None, data, false).gas_consumed
+		};
+		let read = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+		// An unknown function of a known extension traps before anything is charged.
+		let base = gas_consumed(0x0000_0004, Vec::new());
+		assert!(gas_consumed(0x0000_0003, BOB.encode()) >= base + read);
+	});
+}
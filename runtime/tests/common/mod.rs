@@ -0,0 +1,156 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Helpers shared by the runtime tests.
+
+// Every test crate only uses some of them.
+#![allow(dead_code)]
+
+use canvas_runtime::{
+	AccountId, BalancesConfig, BuildStorage, Contracts, GenesisConfig, System, UNIT,
+};
+use pallet_contracts_primitives::{Code, ExecReturnValue};
+use sp_runtime::{AccountId32, DispatchError};
+
+pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId32 = AccountId32::new([2u8; 32]);
+pub const CHARLIE: AccountId32 = AccountId32::new([3u8; 32]);
+
+pub const GAS_LIMIT: u64 = 10_000_000_000;
+
+/// A contract which does nothing, with one page of memory.
+pub const NOOP: &str = r#"
+(module
+	(import "env" "memory" (memory 1 1))
+	(func (export "deploy"))
+	(func (export "call"))
+)
+"#;
+
+/// A contract which forwards its input to `seal_call_chain_extension`.
+///
+/// The input is the `func_id` followed by the input of the function. The contract returns the
+/// status code followed by the output of the function.
+pub const CHAIN_EXTENSION_PROXY: &str = r#"
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) length of the input buffer
+	;; [4, 512) func_id followed by the input of the function
+	;; [512, 516) length of the output buffer
+	;; [516, 520) status code
+	;; [520, 1024) output of the function
+	(data (i32.const 0) "\fc\01")
+	(data (i32.const 512) "\f8\01")
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(i32.store (i32.const 516)
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 4))
+				(i32.const 8)
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))
+				(i32.const 520)
+				(i32.const 512)
+			)
+		)
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 516)
+			(i32.add (i32.load (i32.const 512)) (i32.const 4))
+		)
+	)
+)
+"#;
+
+/// A genesis config in which each of `accounts` owns 1000 units.
+pub fn endowed(accounts: &[AccountId]) -> GenesisConfig {
+	GenesisConfig {
+		balances: BalancesConfig {
+			balances: accounts.iter().cloned().map(|account| (account, 1_000 * UNIT)).collect(),
+		},
+		..Default::default()
+	}
+}
+
+/// Test externalities built from `genesis`, at block 1 so that events are recorded.
+pub fn new_test_ext_with(genesis: GenesisConfig) -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::new(genesis.build_storage().unwrap());
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Test externalities in which Alice and Bob are endowed.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with(endowed(&[ALICE, BOB]))
+}
+
+/// Upload and instantiate the contract in the text format `wat`.
+pub fn instantiate_with(
+	deployer: AccountId,
+	wat: &str,
+	value: u128,
+	data: Vec<u8>,
+	salt: Vec<u8>,
+) -> Result<AccountId, DispatchError> {
+	let code = wat::parse_str(wat).unwrap();
+	Contracts::bare_instantiate(
+		deployer,
+		value,
+		GAS_LIMIT,
+		None,
+		Code::Upload(code.into()),
+		data,
+		salt,
+		false,
+	)
+	.result
+	.map(|result| result.account_id)
+}
+
+/// Instantiate [`NOOP`] endowed with 100 units.
+pub fn instantiate(deployer: AccountId) -> AccountId {
+	instantiate_with(deployer, NOOP, 100 * UNIT, Vec::new(), Vec::new()).unwrap()
+}
+
+/// Call the chain extension function `func_id` through a [`CHAIN_EXTENSION_PROXY`].
+///
+/// Returns the status code and the output of the function. The output is only meaningful for
+/// functions which write one.
+pub fn call_chain_extension(
+	origin: AccountId,
+	proxy: AccountId,
+	func_id: u32,
+	input: Vec<u8>,
+) -> Result<(u32, Vec<u8>), DispatchError> {
+	let data = func_id.to_le_bytes().into_iter().chain(input).collect();
+	let result = Contracts::bare_call(origin, proxy, 0, GAS_LIMIT, None, data, false).result?;
+	let (status, output) = result.data.0.split_at(4);
+	Ok((u32::from_le_bytes(status.try_into().unwrap()), output.to_vec()))
+}
+
+/// Call `contract` with `data`.
+pub fn call(origin: AccountId, contract: AccountId, data: Vec<u8>) -> ExecReturnValue {
+	Contracts::bare_call(origin, contract, 0, GAS_LIMIT, None, data, false)
+		.result
+		.unwrap()
+}
@@ -0,0 +1,130 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the addresses contracts are deployed to.
+
+mod common;
+
+use canvas_runtime::{
+	impls::{universal_salt, UNIVERSAL_SALT_PREFIX},
+	AccountId, Call, Contracts, Origin, Runtime, UNIT,
+};
+use common::{new_test_ext, ALICE, BOB, GAS_LIMIT, NOOP};
+use sp_runtime::traits::{BlakeTwo256, Dispatchable, Hash};
+
+fn instantiate(deployer: AccountId, data: Vec<u8>, salt: Vec<u8>) -> AccountId {
+	common::instantiate_with(deployer, NOOP, 100 * UNIT, data, salt).unwrap()
+}
+
+fn default_address(deployer: &AccountId, code_hash: &sp_core::H256, salt: &[u8]) -> AccountId {
+	<pallet_contracts::DefaultAddressGenerator as pallet_contracts::AddressGenerator<Runtime>>::
+		generate_address(deployer, code_hash, salt)
+}
+
+#[test]
+fn universal_address_is_independent_of_deployer() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"code");
+		let salt = universal_salt(b"input", b"salt");
+
+		assert_eq!(
+			Contracts::contract_address(&ALICE, &code_hash, &salt),
+			Contracts::contract_address(&BOB, &code_hash, &salt),
+		);
+		assert_ne!(
+			Contracts::contract_address(&ALICE, &code_hash, &salt),
+			Contracts::contract_address(&ALICE, &code_hash, &universal_salt(b"input", b"other")),
+		);
+	});
+}
+
+#[test]
+fn universal_address_depends_on_input() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"code");
+
+		assert_ne!(
+			Contracts::contract_address(&ALICE, &code_hash, &universal_salt(b"input", b"salt")),
+			Contracts::contract_address(&ALICE, &code_hash, &universal_salt(b"other", b"salt")),
+		);
+	});
+}
+
+#[test]
+fn other_salts_depend_on_deployer() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"code");
+
+		assert_ne!(
+			Contracts::contract_address(&ALICE, &code_hash, b"salt"),
+			Contracts::contract_address(&BOB, &code_hash, b"salt"),
+		);
+		assert_eq!(
+			Contracts::contract_address(&ALICE, &code_hash, b"salt"),
+			default_address(&ALICE, &code_hash, b"salt"),
+		);
+	});
+}
+
+#[test]
+fn contracts_get_deployer_dependent_addresses() {
+	new_test_ext().execute_with(|| {
+		let contract = common::instantiate(ALICE);
+		let code_hash = BlakeTwo256::hash(b"code");
+		let salt = universal_salt(b"input", b"salt");
+
+		assert_eq!(
+			Contracts::contract_address(&contract, &code_hash, &salt),
+			default_address(&contract, &code_hash, &salt),
+		);
+	});
+}
+
+#[test]
+fn same_address_on_different_chains() {
+	let data = vec![1, 2, 3];
+	let salt = universal_salt(&data, b"salt");
+	let on_first = new_test_ext().execute_with(|| instantiate(ALICE, data.clone(), salt.clone()));
+	let on_second = new_test_ext().execute_with(|| instantiate(BOB, data.clone(), salt.clone()));
+
+	assert_eq!(on_first, on_second);
+}
+
+// `instantiate_with_code` is filtered altogether with `permissioned-code`.
+#[cfg(not(feature = "permissioned-code"))]
+#[test]
+fn universal_salt_must_match_input() {
+	new_test_ext().execute_with(|| {
+		let instantiate = |data: Vec<u8>, salt: Vec<u8>| {
+			Call::Contracts(pallet_contracts::Call::instantiate_with_code {
+				value: 100 * UNIT,
+				gas_limit: GAS_LIMIT,
+				storage_deposit_limit: None,
+				code: wat::parse_str(NOOP).unwrap(),
+				data,
+				salt,
+			})
+			.dispatch(Origin::signed(ALICE))
+		};
+
+		let err = instantiate(vec![1], universal_salt(&[2], b"salt")).unwrap_err();
+		assert_eq!(err.error, frame_system::Error::<Runtime>::CallFiltered.into());
+		let err = instantiate(vec![1], [UNIVERSAL_SALT_PREFIX, b"salt"].concat()).unwrap_err();
+		assert_eq!(err.error, frame_system::Error::<Runtime>::CallFiltered.into());
+
+		assert!(instantiate(vec![1], universal_salt(&[1], b"salt")).is_ok());
+	});
+}
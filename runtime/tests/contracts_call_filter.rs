@@ -0,0 +1,236 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the calls contracts may dispatch through `seal_call_runtime`.
+
+mod common;
+
+use canvas_runtime::{AccountId, Balances, Call, ContractsCallFilter, Origin, Runtime, UNIT};
+use common::{new_test_ext, ALICE, BOB};
+use frame_support::traits::{Contains, OriginTrait};
+use sp_runtime::traits::Dispatchable;
+use xcm::latest::prelude::*;
+
+fn balance_transfer(dest: AccountId, value: u128) -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: dest.into(), value })
+}
+
+fn balance_set(who: AccountId, new_free: u128) -> Call {
+	Call::Balances(pallet_balances::Call::set_balance {
+		who: who.into(),
+		new_free,
+		new_reserved: 0,
+	})
+}
+
+#[test]
+fn filter_allows_transfers() {
+	assert!(ContractsCallFilter::contains(&balance_transfer(BOB, UNIT)));
+	assert!(ContractsCallFilter::contains(&Call::Balances(
+		pallet_balances::Call::transfer_keep_alive { dest: BOB.into(), value: UNIT }
+	)));
+	assert!(ContractsCallFilter::contains(&Call::Assets(pallet_assets::Call::transfer {
+		id: 1,
+		target: BOB.into(),
+		amount: UNIT,
+	})));
+	assert!(ContractsCallFilter::contains(&Call::Assets(pallet_assets::Call::approve_transfer {
+		id: 1,
+		delegate: BOB.into(),
+		amount: UNIT
+	})));
+}
+
+#[test]
+fn filter_rejects_reserve_transfers() {
+	assert!(!ContractsCallFilter::contains(&Call::PolkadotXcm(
+		pallet_xcm::Call::reserve_transfer_assets {
+			dest: Box::new(MultiLocation::parent().into()),
+			beneficiary: Box::new(MultiLocation::parent().into()),
+			assets: Box::new(MultiAssets::new().into()),
+			fee_asset_item: 0,
+		}
+	)));
+	assert!(!ContractsCallFilter::contains(&Call::PolkadotXcm(
+		pallet_xcm::Call::limited_reserve_transfer_assets {
+			dest: Box::new(MultiLocation::parent().into()),
+			beneficiary: Box::new(MultiLocation::parent().into()),
+			assets: Box::new(MultiAssets::new().into()),
+			fee_asset_item: 0,
+			weight_limit: Unlimited,
+		}
+	)));
+}
+
+#[test]
+fn filter_allows_scheduling() {
+	assert!(ContractsCallFilter::contains(&Call::ContractsScheduler(
+		pallet_contracts_scheduler::Call::schedule {
+			id: [0; 32],
+			after: 10,
+			period: None,
+			value: 0,
+			gas_limit: 0,
+			storage_deposit_limit: None,
+			data: Vec::new(),
+		}
+	)));
+	assert!(ContractsCallFilter::contains(&Call::ContractsScheduler(
+		pallet_contracts_scheduler::Call::cancel { id: [0; 32] }
+	)));
+	assert!(!ContractsCallFilter::contains(&Call::Scheduler(pallet_scheduler::Call::schedule {
+		when: 10,
+		maybe_periodic: None,
+		priority: 0,
+		call: Box::new(balance_transfer(BOB, UNIT).into()),
+	})));
+}
+
+#[test]
+fn filter_blocks_privileged_calls() {
+	assert!(!ContractsCallFilter::contains(&balance_set(BOB, UNIT)));
+	assert!(!ContractsCallFilter::contains(&Call::System(frame_system::Call::set_code {
+		code: Vec::new(),
+	})));
+	assert!(!ContractsCallFilter::contains(&Call::Assets(pallet_assets::Call::force_create {
+		id: 1,
+		owner: BOB.into(),
+		is_sufficient: true,
+		min_balance: 1,
+	})));
+	assert!(!ContractsCallFilter::contains(&Call::PolkadotXcm(
+		pallet_xcm::Call::force_default_xcm_version { maybe_xcm_version: None }
+	)));
+}
+
+#[test]
+fn filtered_call_fails_with_call_filtered() {
+	new_test_ext().execute_with(|| {
+		// This is the origin the contracts pallet dispatches `seal_call_runtime` calls with.
+		let mut origin: Origin = frame_system::RawOrigin::Signed(ALICE).into();
+		origin.add_filter(ContractsCallFilter::contains);
+
+		let err = balance_set(BOB, UNIT).dispatch(origin).unwrap_err();
+
+		assert_eq!(err.error, frame_system::Error::<Runtime>::CallFiltered.into());
+	});
+}
+
+#[test]
+fn allowed_call_passes_filter() {
+	new_test_ext().execute_with(|| {
+		let mut origin: Origin = frame_system::RawOrigin::Signed(ALICE).into();
+		origin.add_filter(ContractsCallFilter::contains);
+		let bob_before = Balances::free_balance(&BOB);
+
+		assert!(balance_transfer(BOB, UNIT).dispatch(origin).is_ok());
+
+		assert_eq!(Balances::free_balance(&BOB), bob_before + UNIT);
+	});
+}
+
+/// `seal_call_runtime` is only available with the unstable contracts interface.
+#[cfg(feature = "contracts-unstable-interface")]
+mod call_runtime {
+	use super::*;
+	use canvas_runtime::Contracts;
+	use codec::Encode;
+	use pallet_contracts_primitives::Code;
+
+	const GAS_LIMIT: u64 = 100_000_000_000;
+
+	/// Dispatches the call passed as input and returns the return code of `seal_call_runtime`.
+	const CALL_RUNTIME: &str = r#"
+(module
+	(import "__unstable__" "seal_call_runtime" (func $seal_call_runtime (param i32 i32) (result i32)))
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; Store available input size at offset 0.
+		(i32.store (i32.const 0) (i32.const 512))
+		;; Read the encoded call to offset 4.
+		(call $seal_input (i32.const 4) (i32.const 0))
+		;; Dispatch the call and store the return code at offset 0.
+		(i32.store
+			(i32.const 0)
+			(call $seal_call_runtime (i32.const 4) (i32.load (i32.const 0)))
+		)
+		(call $seal_return (i32.const 0) (i32.const 0) (i32.const 4))
+	)
+)
+"#;
+
+	/// `ReturnCode::Success` of the contracts pallet.
+	const SUCCESS: u32 = 0;
+	/// `ReturnCode::CallRuntimeReturnedError` of the contracts pallet.
+	const CALL_RUNTIME_RETURNED_ERROR: u32 = 10;
+
+	fn instantiate() -> AccountId {
+		let code = wat::parse_str(CALL_RUNTIME).unwrap();
+		Contracts::bare_instantiate(
+			ALICE,
+			100 * UNIT,
+			GAS_LIMIT,
+			None,
+			Code::Upload(code.into()),
+			Vec::new(),
+			Vec::new(),
+			false,
+		)
+		.result
+		.unwrap()
+		.account_id
+	}
+
+	fn call_runtime(contract: &AccountId, call: Call) -> u32 {
+		let result =
+			Contracts::bare_call(ALICE, contract.clone(), 0, GAS_LIMIT, None, call.encode(), false)
+				.result
+				.unwrap();
+		u32::from_le_bytes(result.data.0.try_into().unwrap())
+	}
+
+	#[test]
+	fn allowed_call_is_dispatched() {
+		new_test_ext().execute_with(|| {
+			let contract = instantiate();
+			let bob_before = Balances::free_balance(&BOB);
+
+			assert_eq!(call_runtime(&contract, balance_transfer(BOB, UNIT)), SUCCESS);
+
+			assert_eq!(Balances::free_balance(&BOB), bob_before + UNIT);
+		});
+	}
+
+	#[test]
+	fn filtered_call_fails() {
+		new_test_ext().execute_with(|| {
+			let contract = instantiate();
+			let contract_before = Balances::free_balance(&contract);
+
+			assert_eq!(
+				call_runtime(&contract, balance_set(contract.clone(), 1_000_000 * UNIT)),
+				CALL_RUNTIME_RETURNED_ERROR
+			);
+
+			assert_eq!(Balances::free_balance(&contract), contract_before);
+		});
+	}
+}
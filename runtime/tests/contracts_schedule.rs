@@ -0,0 +1,165 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the on-chain overrides of the contracts schedule.
+
+mod common;
+
+use canvas_runtime::{ContractsSchedule, Origin, Runtime, Schedule, UNIT};
+use common::{new_test_ext, ALICE, NOOP};
+use frame_support::traits::Get;
+
+fn instantiate() -> bool {
+	common::instantiate_with(ALICE, NOOP, UNIT, Vec::new(), Vec::new()).is_ok()
+}
+
+#[test]
+fn default_schedule_without_overrides() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Schedule::get(), pallet_contracts::Schedule::default());
+	});
+}
+
+#[test]
+fn limits_are_overridden() {
+	new_test_ext().execute_with(|| {
+		let default = pallet_contracts::Schedule::<Runtime>::default();
+		// Too short for the code of `NOOP`.
+		let limits = pallet_contracts::Limits { code_len: 16, ..default.limits.clone() };
+
+		assert!(ContractsSchedule::set_limits(Origin::root(), Some(limits.clone())).is_ok());
+
+		let schedule = Schedule::get();
+		assert_eq!(schedule.limits, limits);
+		assert!(schedule.instruction_weights.version > default.instruction_weights.version);
+		assert!(!instantiate());
+
+		assert!(ContractsSchedule::set_limits(Origin::root(), None).is_ok());
+
+		assert_eq!(Schedule::get().limits, default.limits);
+		assert!(instantiate());
+	});
+}
+
+#[test]
+fn limits_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let default = pallet_contracts::Limits::default();
+		let set_limits = |limits| ContractsSchedule::set_limits(Origin::root(), Some(limits));
+		let out_of_bounds: sp_runtime::DispatchError =
+			pallet_contracts_schedule::Error::<Runtime>::LimitOutOfBounds.into();
+
+		assert_eq!(
+			set_limits(pallet_contracts::Limits { memory_pages: 0, ..default.clone() }),
+			Err(out_of_bounds),
+		);
+		assert_eq!(
+			set_limits(pallet_contracts::Limits {
+				code_len: default.code_len * 4 + 1,
+				..default.clone()
+			}),
+			Err(out_of_bounds),
+		);
+		assert_eq!(ContractsSchedule::overrides().limits, None);
+
+		assert!(set_limits(pallet_contracts::Limits {
+			code_len: default.code_len * 4,
+			..default.clone()
+		})
+		.is_ok());
+	});
+}
+
+#[test]
+fn host_fn_weights_are_overridden() {
+	new_test_ext().execute_with(|| {
+		let default = pallet_contracts::Schedule::<Runtime>::default();
+		let host_fn_weights = pallet_contracts::HostFnWeights {
+			caller: default.host_fn_weights.caller * 2,
+			..default.host_fn_weights.clone()
+		};
+
+		assert!(ContractsSchedule::set_host_fn_weights(
+			Origin::root(),
+			Some(host_fn_weights.clone())
+		)
+		.is_ok());
+
+		let schedule = Schedule::get();
+		assert_eq!(schedule.host_fn_weights, host_fn_weights);
+		// Host function weights are not part of instrumented code.
+		assert_eq!(schedule.instruction_weights, default.instruction_weights);
+	});
+}
+
+#[test]
+fn host_fn_weights_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let default = pallet_contracts::HostFnWeights::<Runtime>::default();
+		let set_weights = |caller| {
+			ContractsSchedule::set_host_fn_weights(
+				Origin::root(),
+				Some(pallet_contracts::HostFnWeights { caller, ..default.clone() }),
+			)
+		};
+		let out_of_bounds: sp_runtime::DispatchError =
+			pallet_contracts_schedule::Error::<Runtime>::WeightOutOfBounds.into();
+
+		assert_eq!(set_weights(0), Err(out_of_bounds));
+		assert_eq!(set_weights(default.caller / 4 - 1), Err(out_of_bounds));
+		assert_eq!(set_weights(default.caller * 4 + 1), Err(out_of_bounds));
+		assert_eq!(ContractsSchedule::overrides().host_fn_weights, None);
+
+		assert!(set_weights(default.caller / 4).is_ok());
+		assert!(set_weights(default.caller * 4).is_ok());
+	});
+}
+
+#[test]
+fn instruction_weights_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let default = pallet_contracts::InstructionWeights::<Runtime>::default();
+		let set_weights = |i64const| {
+			ContractsSchedule::set_instruction_weights(
+				Origin::root(),
+				Some(pallet_contracts::InstructionWeights { i64const, ..default.clone() }),
+			)
+		};
+		let out_of_bounds: sp_runtime::DispatchError =
+			pallet_contracts_schedule::Error::<Runtime>::WeightOutOfBounds.into();
+
+		assert_eq!(set_weights(0), Err(out_of_bounds));
+		assert_eq!(set_weights(default.i64const * 4 + 1), Err(out_of_bounds));
+		assert_eq!(ContractsSchedule::overrides().instruction_weights, None);
+
+		assert!(set_weights(default.i64const * 4).is_ok());
+		// The version is managed by the pallet and not bounded.
+		assert!(ContractsSchedule::set_instruction_weights(
+			Origin::root(),
+			Some(pallet_contracts::InstructionWeights { version: 0, ..default.clone() }),
+		)
+		.is_ok());
+	});
+}
+
+#[test]
+fn only_governance_sets_overrides() {
+	new_test_ext().execute_with(|| {
+		assert!(ContractsSchedule::set_limits(Origin::signed(ALICE), None).is_err());
+		assert!(ContractsSchedule::set_host_fn_weights(Origin::signed(ALICE), None).is_err());
+		assert!(ContractsSchedule::set_instruction_weights(Origin::signed(ALICE), None).is_err());
+	});
+}
@@ -0,0 +1,188 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for calls contracts schedule to themselves.
+
+mod common;
+
+use canvas_runtime::{
+	AccountId, Balances, BalancesConfig, CollatorSelection, ContractsScheduler, Event,
+	GenesisConfig, Origin, Runtime, Scheduler, System, UNIT,
+};
+use common::{ALICE, GAS_LIMIT};
+use frame_support::{dispatch::DispatchResult, traits::OnInitialize};
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	common::new_test_ext_with(GenesisConfig {
+		balances: BalancesConfig {
+			// The fees are below the existential deposit, so the pot has to exist.
+			balances: vec![(ALICE, 1_000 * UNIT), (CollatorSelection::account_id(), UNIT)],
+		},
+		..Default::default()
+	})
+}
+
+fn instantiate() -> AccountId {
+	common::instantiate(ALICE)
+}
+
+/// Schedules a call of `contract` to itself, as `seal_call_runtime` would dispatch it.
+fn schedule(contract: &AccountId, id: u8, after: u32) -> DispatchResult {
+	schedule_periodic(contract, id, after, None)
+}
+
+fn schedule_periodic(
+	contract: &AccountId,
+	id: u8,
+	after: u32,
+	period: Option<(u32, u32)>,
+) -> DispatchResult {
+	ContractsScheduler::schedule(
+		Origin::signed(contract.clone()),
+		[id; 32],
+		after,
+		period,
+		0,
+		GAS_LIMIT,
+		None,
+		Vec::new(),
+	)
+}
+
+fn executions() -> usize {
+	System::events()
+		.iter()
+		.filter(|record| {
+			matches!(
+				record.event,
+				Event::ContractsScheduler(pallet_contracts_scheduler::Event::Executed {
+					result: Ok(()),
+					..
+				})
+			)
+		})
+		.count()
+}
+
+fn executed() -> bool {
+	executions() > 0
+}
+
+fn run_to_block(n: u32) {
+	System::set_block_number(n);
+	Scheduler::on_initialize(n);
+}
+
+#[test]
+fn scheduled_call_is_executed_and_paid_for() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate();
+		let reserved = Balances::reserved_balance(&contract);
+		let pot = Balances::free_balance(&CollatorSelection::account_id());
+
+		assert!(schedule(&contract, 0, 1).is_ok());
+		assert!(Balances::reserved_balance(&contract) > reserved);
+
+		// `after` counts from the next block.
+		run_to_block(3);
+
+		assert!(executed());
+		assert_eq!(Balances::reserved_balance(&contract), reserved);
+		assert!(Balances::free_balance(&CollatorSelection::account_id()) > pot);
+		assert!(pallet_contracts_scheduler::Scheduled::<Runtime>::get(&contract, [0; 32]).is_none());
+	});
+}
+
+#[test]
+fn periodic_call_is_executed_every_period() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate();
+		let reserved = Balances::reserved_balance(&contract);
+
+		assert!(schedule_periodic(&contract, 0, 1, Some((2, 2))).is_ok());
+		let reserved_for_both = Balances::reserved_balance(&contract);
+
+		run_to_block(3);
+		assert_eq!(executions(), 1);
+		let reserved_for_one = Balances::reserved_balance(&contract);
+		assert!(reserved_for_one > reserved && reserved_for_one < reserved_for_both);
+		assert!(pallet_contracts_scheduler::Scheduled::<Runtime>::get(&contract, [0; 32]).is_some());
+
+		run_to_block(5);
+		assert_eq!(executions(), 2);
+		assert_eq!(Balances::reserved_balance(&contract), reserved);
+		assert!(pallet_contracts_scheduler::Scheduled::<Runtime>::get(&contract, [0; 32]).is_none());
+	});
+}
+
+#[test]
+fn periodic_call_needs_a_period() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate();
+
+		assert!(schedule_periodic(&contract, 0, 1, Some((0, 2))).is_err());
+		assert!(schedule_periodic(&contract, 0, 1, Some((2, 0))).is_err());
+	});
+}
+
+#[test]
+fn cancelled_call_is_refunded() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate();
+		let free = Balances::free_balance(&contract);
+
+		assert!(schedule(&contract, 0, 1).is_ok());
+		assert!(ContractsScheduler::cancel(Origin::signed(contract.clone()), [0; 32]).is_ok());
+
+		run_to_block(3);
+
+		assert!(!executed());
+		assert_eq!(Balances::free_balance(&contract), free);
+	});
+}
+
+#[test]
+fn only_contracts_can_schedule() {
+	new_test_ext().execute_with(|| {
+		assert!(schedule(&ALICE, 0, 1).is_err());
+	});
+}
+
+#[test]
+fn scheduling_is_bounded() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate();
+
+		assert!(ContractsScheduler::schedule(
+			Origin::signed(contract.clone()),
+			[0; 32],
+			1,
+			None,
+			0,
+			u64::MAX,
+			None,
+			Vec::new(),
+		)
+		.is_err());
+
+		assert!(schedule(&contract, 0, 1).is_ok());
+		assert!(schedule(&contract, 0, 2).is_err());
+		for id in 1..16 {
+			assert!(schedule(&contract, id, 1).is_ok());
+		}
+		assert!(schedule(&contract, 16, 1).is_err());
+	});
+}
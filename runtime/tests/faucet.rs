@@ -0,0 +1,116 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the faucet of the `faucet` feature.
+
+#![cfg(feature = "faucet")]
+
+mod common;
+
+use canvas_runtime::{
+	Balances, Faucet, FaucetConfig, GenesisConfig, Origin, Runtime, System, EXISTENTIAL_DEPOSIT,
+	UNIT,
+};
+use common::ALICE;
+use frame_support::{pallet_prelude::ValidateUnsigned, traits::Currency};
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	AccountId32,
+};
+
+const PERIOD: u32 = 10;
+
+fn new_test_ext(pot_balance: u128) -> sp_io::TestExternalities {
+	new_test_ext_claiming(pot_balance, 100 * UNIT)
+}
+
+fn new_test_ext_claiming(pot_balance: u128, claim_amount: u128) -> sp_io::TestExternalities {
+	common::new_test_ext_with(GenesisConfig {
+		faucet: FaucetConfig { pot_balance, claim_amount, claim_period: PERIOD },
+		..Default::default()
+	})
+}
+
+fn validate(dest: AccountId32) -> Result<(), InvalidTransaction> {
+	let call = pallet_faucet::Call::claim { dest };
+	Faucet::validate_unsigned(TransactionSource::External, &call)
+		.map(|_| ())
+		.map_err(|err| match err {
+			sp_runtime::transaction_validity::TransactionValidityError::Invalid(invalid) => invalid,
+			_ => panic!("unexpected validity error"),
+		})
+}
+
+#[test]
+fn genesis_funds_pot() {
+	new_test_ext(1_000 * UNIT).execute_with(|| {
+		assert_eq!(Balances::free_balance(&Faucet::account_id()), 1_000 * UNIT);
+	});
+}
+
+#[test]
+fn empty_account_claims() {
+	new_test_ext(1_000 * UNIT).execute_with(|| {
+		assert!(validate(ALICE).is_ok());
+		assert!(Faucet::claim(Origin::none(), ALICE).is_ok());
+
+		assert_eq!(Balances::free_balance(&ALICE), 100 * UNIT);
+	});
+}
+
+#[test]
+fn claims_are_rate_limited() {
+	new_test_ext(1_000 * UNIT).execute_with(|| {
+		assert!(Faucet::claim(Origin::none(), ALICE).is_ok());
+
+		System::set_block_number(PERIOD);
+		assert_eq!(validate(ALICE), Err(InvalidTransaction::Custom(pallet_faucet::RATE_LIMITED)));
+		assert!(Faucet::claim(Origin::none(), ALICE).is_err());
+
+		System::set_block_number(PERIOD + 1);
+		assert!(validate(ALICE).is_ok());
+		assert!(Faucet::claim(Origin::none(), ALICE).is_ok());
+		assert_eq!(Balances::free_balance(&ALICE), 200 * UNIT);
+	});
+}
+
+#[test]
+fn claims_stop_when_pot_is_exhausted() {
+	new_test_ext(150 * UNIT).execute_with(|| {
+		assert!(Faucet::claim(Origin::none(), ALICE).is_ok());
+
+		System::set_block_number(PERIOD + 1);
+		assert_eq!(validate(ALICE), Err(InvalidTransaction::Custom(pallet_faucet::POT_EXHAUSTED)));
+		assert!(Balances::free_balance(&Faucet::account_id()) >= Balances::minimum_balance());
+	});
+}
+
+#[test]
+fn failed_transfers_return_their_error() {
+	new_test_ext_claiming(1_000 * UNIT, EXISTENTIAL_DEPOSIT - 1).execute_with(|| {
+		assert_eq!(
+			Faucet::claim(Origin::none(), ALICE),
+			Err(pallet_balances::Error::<Runtime>::ExistentialDeposit.into())
+		);
+	});
+}
+
+#[test]
+fn claims_must_be_unsigned() {
+	new_test_ext(1_000 * UNIT).execute_with(|| {
+		assert!(Faucet::claim(Origin::signed(ALICE), ALICE).is_err());
+	});
+}
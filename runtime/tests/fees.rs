@@ -0,0 +1,72 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for where transaction fees and dust end up.
+
+mod common;
+
+use canvas_runtime::{
+	impls::{DealWithFees, ToStakingPot},
+	Balances, CollatorSelection, UNIT,
+};
+use common::{new_test_ext, ALICE};
+use frame_support::traits::{Currency, ExistenceRequirement, OnUnbalanced, WithdrawReasons};
+
+fn pot_balance() -> u128 {
+	Balances::free_balance(&CollatorSelection::account_id())
+}
+
+#[test]
+fn fees_and_tips_go_to_pot_without_author() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+		let fees = Balances::withdraw(
+			&ALICE,
+			10 * UNIT,
+			WithdrawReasons::FEE,
+			ExistenceRequirement::KeepAlive,
+		)
+		.unwrap();
+		let tips =
+			Balances::withdraw(&ALICE, UNIT, WithdrawReasons::TIP, ExistenceRequirement::KeepAlive)
+				.unwrap();
+
+		DealWithFees::on_unbalanceds(vec![fees, tips].into_iter());
+
+		// No block author in tests, so the author's share goes to the pot as well.
+		assert_eq!(pot_balance(), 11 * UNIT);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+#[test]
+fn dust_goes_to_pot() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+		let dust = Balances::withdraw(
+			&ALICE,
+			UNIT,
+			WithdrawReasons::TRANSFER,
+			ExistenceRequirement::KeepAlive,
+		)
+		.unwrap();
+
+		ToStakingPot::on_unbalanced(dust);
+
+		assert_eq!(pot_balance(), UNIT);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the contracts instantiated in the genesis.
+
+mod common;
+
+use canvas_runtime::{AccountId, Balances, Contracts, GenesisConfig, GenesisContractsConfig, UNIT};
+use common::{ALICE, NOOP};
+use pallet_genesis_contracts::GenesisContract;
+use sp_runtime::traits::Hash;
+
+/// A contract whose constructor always reverts.
+const REVERT: &str = r#"
+(module
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+	(func (export "deploy")
+		(call $seal_return (i32.const 1) (i32.const 0) (i32.const 0))
+	)
+	(func (export "call"))
+)
+"#;
+
+fn genesis_contract(wat: &str, salt: &[u8]) -> GenesisContract<AccountId, u128> {
+	GenesisContract {
+		deployer: ALICE,
+		code: wat::parse_str(wat).unwrap(),
+		data: vec![0x9b, 0xae, 0x9d, 0x5e],
+		endowment: 10 * UNIT,
+		salt: salt.to_vec(),
+	}
+}
+
+fn new_test_ext(contracts: Vec<GenesisContract<AccountId, u128>>) -> sp_io::TestExternalities {
+	common::new_test_ext_with(GenesisConfig {
+		genesis_contracts: GenesisContractsConfig { contracts },
+		..common::endowed(&[ALICE])
+	})
+}
+
+#[test]
+fn contracts_exist_at_known_addresses() {
+	let code_hash = sp_runtime::traits::BlakeTwo256::hash(&wat::parse_str(NOOP).unwrap());
+	let first = Contracts::contract_address(&ALICE, &code_hash, b"first");
+	let second = Contracts::contract_address(&ALICE, &code_hash, b"second");
+
+	new_test_ext(vec![genesis_contract(NOOP, b"first"), genesis_contract(NOOP, b"second")])
+		.execute_with(|| {
+			assert!(Contracts::get_storage(first.clone(), Default::default()).is_ok());
+			assert!(Contracts::get_storage(second.clone(), Default::default()).is_ok());
+			assert!(Balances::total_balance(&first) >= 10 * UNIT);
+			assert!(Balances::total_balance(&second) >= 10 * UNIT);
+		});
+}
+
+#[test]
+#[should_panic(expected = "reverted")]
+fn reverting_constructor_fails_the_genesis() {
+	new_test_ext(vec![genesis_contract(REVERT, b"salt")]);
+}
+
+#[cfg(feature = "permissioned-code")]
+#[test]
+fn genesis_code_is_approved() {
+	let code_hash = sp_runtime::traits::BlakeTwo256::hash(&wat::parse_str(NOOP).unwrap());
+
+	new_test_ext(vec![genesis_contract(NOOP, b"salt")]).execute_with(|| {
+		assert!(canvas_runtime::CodePermissions::is_approved(&code_hash));
+	});
+}
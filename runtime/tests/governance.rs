@@ -0,0 +1,155 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the local council and democracy.
+
+mod common;
+
+use canvas_runtime::{
+	Call, CollatorSelectionUpdateOrigin, Council, CouncilCollective, CouncilConfig, Democracy,
+	Event, GenesisConfig, Origin, ParachainUpgrade, Scheduler, System, TechnicalCollective,
+	TechnicalCommitteeConfig, UNIT,
+};
+use codec::Encode;
+use common::{ALICE, BOB, CHARLIE};
+use frame_support::{
+	traits::{EnsureOrigin, OnInitialize},
+	weights::GetDispatchInfo,
+};
+use pallet_democracy::{AccountVote, Conviction, Vote};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	common::new_test_ext_with(GenesisConfig {
+		council: CouncilConfig { members: vec![ALICE, BOB], phantom: Default::default() },
+		technical_committee: TechnicalCommitteeConfig {
+			members: vec![ALICE, BOB],
+			phantom: Default::default(),
+		},
+		..common::endowed(&[ALICE, BOB, CHARLIE])
+	})
+}
+
+fn council(yes: u32, all: u32) -> Origin {
+	pallet_collective::RawOrigin::<_, CouncilCollective>::Members(yes, all).into()
+}
+
+fn technical_committee(yes: u32, all: u32) -> Origin {
+	pallet_collective::RawOrigin::<_, TechnicalCollective>::Members(yes, all).into()
+}
+
+#[test]
+fn council_majority_is_governance_origin() {
+	new_test_ext().execute_with(|| {
+		assert!(CollatorSelectionUpdateOrigin::try_origin(Origin::root()).is_ok());
+		assert!(CollatorSelectionUpdateOrigin::try_origin(council(2, 3)).is_ok());
+
+		assert!(CollatorSelectionUpdateOrigin::try_origin(council(1, 2)).is_err());
+		assert!(CollatorSelectionUpdateOrigin::try_origin(technical_committee(2, 2)).is_err());
+		assert!(CollatorSelectionUpdateOrigin::try_origin(Origin::signed(ALICE)).is_err());
+	});
+}
+
+#[test]
+fn fast_tracked_referendum_authorizes_upgrade() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"new runtime");
+		let proposal =
+			Call::ParachainSystem(cumulus_pallet_parachain_system::Call::authorize_upgrade {
+				code_hash,
+			})
+			.encode();
+		let proposal_hash = BlakeTwo256::hash(&proposal);
+
+		assert!(Democracy::note_preimage(Origin::signed(CHARLIE), proposal).is_ok());
+		assert!(Democracy::external_propose_majority(council(2, 2), proposal_hash).is_ok());
+		assert!(Democracy::fast_track(technical_committee(2, 2), proposal_hash, 1, 1).is_ok());
+		let vote = AccountVote::Standard {
+			vote: Vote { aye: true, conviction: Conviction::None },
+			balance: 100 * UNIT,
+		};
+		assert!(Democracy::vote(Origin::signed(CHARLIE), 0, vote).is_ok());
+
+		// The referendum ends in block 2 and is enacted one block later.
+		System::set_block_number(2);
+		Democracy::on_initialize(2);
+		System::set_block_number(3);
+		Scheduler::on_initialize(3);
+
+		assert!(upgrade_authorized());
+	});
+}
+
+fn upgrade_authorized() -> bool {
+	System::events().iter().any(|record| {
+		matches!(
+			record.event,
+			Event::ParachainSystem(
+				cumulus_pallet_parachain_system::Event::UpgradeAuthorized { .. }
+			)
+		)
+	})
+}
+
+#[test]
+fn council_motion_authorizes_upgrade() {
+	new_test_ext().execute_with(|| {
+		let proposal = Call::ParachainUpgrade(pallet_parachain_upgrade::Call::authorize_upgrade {
+			code_hash: BlakeTwo256::hash(b"new runtime"),
+		});
+		let proposal_len = proposal.encoded_size() as u32;
+		let proposal_weight = proposal.get_dispatch_info().weight;
+		let proposal_hash = BlakeTwo256::hash_of(&proposal);
+
+		assert!(
+			Council::propose(Origin::signed(ALICE), 2, Box::new(proposal), proposal_len).is_ok()
+		);
+		assert!(Council::vote(Origin::signed(BOB), proposal_hash, 0, true).is_ok());
+		assert!(Council::close(
+			Origin::signed(ALICE),
+			proposal_hash,
+			0,
+			proposal_weight,
+			proposal_len
+		)
+		.is_ok());
+
+		assert!(upgrade_authorized());
+	});
+}
+
+#[test]
+fn only_governance_origin_upgrades() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"new runtime");
+
+		assert!(ParachainUpgrade::authorize_upgrade(council(1, 2), code_hash).is_err());
+		assert!(ParachainUpgrade::authorize_upgrade(Origin::signed(ALICE), code_hash).is_err());
+		assert!(ParachainUpgrade::enact_authorized_upgrade(council(1, 2), Vec::new()).is_err());
+		assert!(!upgrade_authorized());
+
+		assert!(ParachainUpgrade::authorize_upgrade(council(2, 2), code_hash).is_ok());
+		assert!(upgrade_authorized());
+
+		// The council passes the origin check, but only the authorized code can be enacted.
+		assert_eq!(
+			ParachainUpgrade::enact_authorized_upgrade(council(2, 2), b"other runtime".to_vec())
+				.map_err(|err| err.error),
+			Err(cumulus_pallet_parachain_system::Error::<canvas_runtime::Runtime>::Unauthorized
+				.into())
+		);
+	});
+}
@@ -0,0 +1,163 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for restricting code uploads with the `permissioned-code` feature.
+
+#![cfg(feature = "permissioned-code")]
+
+mod common;
+
+use canvas_runtime::{
+	Call, CodePermissions, CodePermissionsConfig, GenesisConfig, Origin, Runtime, UNIT,
+};
+use codec::Encode;
+use common::{ALICE, BOB, GAS_LIMIT, NOOP};
+use sp_runtime::traits::{BlakeTwo256, Dispatchable, Hash};
+
+/// Instantiates the code hash it is called with, transferring the value following it. Returns
+/// the status code of `seal_instantiate`.
+const INSTANTIATE_PROXY: &str = r#"
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_instantiate" (func $seal_instantiate
+		(param i32 i32 i64 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)
+	))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) length of the input buffer: code hash and value
+	(data (i32.const 0) "\30")
+
+	;; [4, 36) code hash, [36, 52) value
+
+	;; [52, 56) length of the address buffer
+	(data (i32.const 52) "\20")
+
+	;; [56, 88) address, [88, 92) status
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(i32.store (i32.const 88)
+			(call $seal_instantiate
+				(i32.const 4) (i32.const 32) ;; code hash
+				(i64.const 0) ;; all the remaining gas
+				(i32.const 36) (i32.const 16) ;; value
+				(i32.const 0) (i32.const 0) ;; no input
+				(i32.const 56) (i32.const 52) ;; address
+				(i32.const 4294967295) (i32.const 0) ;; no output
+				(i32.const 0) (i32.const 0) ;; no salt
+			)
+		)
+		(call $seal_return (i32.const 0) (i32.const 88) (i32.const 4))
+	)
+)
+"#;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	common::new_test_ext_with(GenesisConfig {
+		code_permissions: CodePermissionsConfig {
+			uploaders: vec![ALICE],
+			approved_code: Vec::new(),
+		},
+		..common::endowed(&[ALICE, BOB])
+	})
+}
+
+fn code() -> Vec<u8> {
+	wat::parse_str(NOOP).unwrap()
+}
+
+fn instantiate(code_hash: sp_core::H256) -> Call {
+	Call::Contracts(pallet_contracts::Call::instantiate {
+		value: UNIT,
+		gas_limit: GAS_LIMIT,
+		storage_deposit_limit: None,
+		code_hash,
+		data: Vec::new(),
+		salt: Vec::new(),
+	})
+}
+
+fn is_filtered(result: frame_support::dispatch::DispatchResultWithPostInfo) -> bool {
+	matches!(
+		result,
+		Err(err) if err.error == frame_system::Error::<Runtime>::CallFiltered.into()
+	)
+}
+
+#[test]
+fn contracts_upload_is_filtered() {
+	new_test_ext().execute_with(|| {
+		let upload = Call::Contracts(pallet_contracts::Call::upload_code {
+			code: code(),
+			storage_deposit_limit: None,
+		});
+
+		assert!(is_filtered(upload.dispatch(Origin::signed(ALICE))));
+	});
+}
+
+#[test]
+fn only_uploaders_upload() {
+	new_test_ext().execute_with(|| {
+		assert!(CodePermissions::upload_code(Origin::signed(BOB), code(), None).is_err());
+		assert!(CodePermissions::upload_code(Origin::signed(ALICE), code(), None).is_ok());
+
+		assert!(CodePermissions::is_approved(&BlakeTwo256::hash(&code())));
+	});
+}
+
+#[test]
+fn anyone_instantiates_approved_code() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(&code());
+		assert!(CodePermissions::upload_code(Origin::signed(ALICE), code(), None).is_ok());
+
+		assert!(instantiate(code_hash).dispatch(Origin::signed(BOB)).is_ok());
+
+		assert!(CodePermissions::revoke_code(Origin::root(), code_hash).is_ok());
+
+		assert!(is_filtered(instantiate(code_hash).dispatch(Origin::signed(BOB))));
+	});
+}
+
+#[test]
+fn only_governance_manages_permissions() {
+	new_test_ext().execute_with(|| {
+		assert!(CodePermissions::add_uploader(Origin::signed(ALICE), BOB).is_err());
+		assert!(CodePermissions::add_uploader(Origin::root(), BOB).is_ok());
+
+		assert!(CodePermissions::is_uploader(&BOB));
+	});
+}
+
+#[test]
+fn contracts_instantiate_revoked_code() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(&code());
+		assert!(CodePermissions::upload_code(Origin::signed(ALICE), code(), None).is_ok());
+		assert!(CodePermissions::revoke_code(Origin::root(), code_hash).is_ok());
+		let proxy =
+			common::instantiate_with(ALICE, INSTANTIATE_PROXY, 100 * UNIT, Vec::new(), Vec::new())
+				.unwrap();
+
+		// `seal_instantiate` is not dispatched as a call, so the filter doesn't apply.
+		let result = common::call(BOB, proxy, (code_hash, UNIT).encode());
+		assert_eq!(u32::from_le_bytes(result.data[..].try_into().unwrap()), 0);
+	});
+}
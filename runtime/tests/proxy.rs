@@ -0,0 +1,74 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the calls proxies may dispatch.
+
+mod common;
+
+use canvas_runtime::{Balances, Call, Event, Origin, Proxy, ProxyType, System, UNIT};
+use common::{new_test_ext, ALICE, BOB};
+use frame_support::traits::InstanceFilter;
+use sp_runtime::MultiAddress;
+
+fn contracts_call() -> Call {
+	Call::Contracts(pallet_contracts::Call::call {
+		dest: MultiAddress::Id(BOB),
+		value: 0,
+		gas_limit: 0,
+		storage_deposit_limit: None,
+		data: Vec::new(),
+	})
+}
+
+fn transfer() -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: MultiAddress::Id(BOB), value: UNIT })
+}
+
+#[test]
+fn contracts_proxy_only_allows_contracts_calls() {
+	assert!(ProxyType::Contracts.filter(&contracts_call()));
+	assert!(ProxyType::Contracts
+		.filter(&Call::Utility(pallet_utility::Call::batch { calls: vec![contracts_call()] })));
+
+	assert!(!ProxyType::Contracts.filter(&transfer()));
+	assert!(!ProxyType::NonTransfer.filter(&contracts_call()));
+	assert!(!ProxyType::NonTransfer.filter(&transfer()));
+}
+
+#[test]
+fn proxy_types_are_ordered() {
+	assert!(ProxyType::Any.is_superset(&ProxyType::Contracts));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::CancelProxy));
+	assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Contracts));
+	assert!(!ProxyType::Contracts.is_superset(&ProxyType::Any));
+}
+
+#[test]
+fn contracts_proxy_cannot_transfer_in_batch() {
+	new_test_ext().execute_with(|| {
+		assert!(Proxy::add_proxy(Origin::signed(ALICE), BOB, ProxyType::Contracts, 0).is_ok());
+		let free = Balances::free_balance(&ALICE);
+
+		let batch = Call::Utility(pallet_utility::Call::batch { calls: vec![transfer()] });
+		assert!(Proxy::proxy(Origin::signed(BOB), ALICE, None, Box::new(batch)).is_ok());
+
+		assert_eq!(Balances::free_balance(&ALICE), free);
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::Utility(pallet_utility::Event::BatchInterrupted { .. })
+		)));
+	});
+}
@@ -0,0 +1,79 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the randomness exposed through `RelayRandomnessApi` and the removal of
+//! `pallet-randomness-collective-flip`.
+
+mod common;
+
+use canvas_runtime::{
+	Block, BlockNumber, Hash, RelayRandomness, RemoveCollectiveFlip, Runtime, System,
+};
+use common::new_test_ext;
+use frame_support::{
+	storage::{storage_prefix, unhashed},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, Randomness, StorageVersion},
+};
+use pallet_relay_randomness::runtime_decl_for_RelayRandomnessApi::RelayRandomnessApi;
+
+fn relay_randomness() -> Option<[u8; 32]> {
+	<Runtime as RelayRandomnessApi<Block, Hash, BlockNumber>>::relay_randomness()
+}
+
+fn random(subject: &[u8]) -> (Hash, BlockNumber) {
+	<Runtime as RelayRandomnessApi<Block, Hash, BlockNumber>>::random(subject.to_vec())
+}
+
+#[test]
+fn api_falls_back_to_parent_hash() {
+	new_test_ext().execute_with(|| {
+		System::set_parent_hash(Hash::repeat_byte(1));
+
+		assert_eq!(relay_randomness(), None);
+		assert_eq!(random(b"subject"), RelayRandomness::random(b"subject"));
+	});
+}
+
+#[test]
+fn api_returns_what_contracts_see() {
+	new_test_ext().execute_with(|| {
+		pallet_relay_randomness::RelayRandomness::<Runtime>::put([7u8; 32]);
+		pallet_relay_randomness::LastUpdated::<Runtime>::put(1);
+
+		assert_eq!(relay_randomness(), Some([7u8; 32]));
+		assert_eq!(random(b"subject"), RelayRandomness::random(b"subject"));
+		assert_eq!(random(b"subject").1, 1);
+		assert_ne!(random(b"subject"), random(b"other subject"));
+	});
+}
+
+#[test]
+fn collective_flip_storage_is_removed_once() {
+	new_test_ext().execute_with(|| {
+		let key = storage_prefix(b"RandomnessCollectiveFlip", b"RandomMaterial");
+		unhashed::put(&key, &vec![Hash::repeat_byte(1)]);
+
+		// Chains starting with `pallet-relay-randomness` never had the storage.
+		assert_eq!(RelayRandomness::on_chain_storage_version(), 1);
+		RemoveCollectiveFlip::on_runtime_upgrade();
+		assert!(unhashed::exists(&key));
+
+		StorageVersion::new(0).put::<RelayRandomness>();
+		RemoveCollectiveFlip::on_runtime_upgrade();
+		assert!(!unhashed::exists(&key));
+		assert_eq!(RelayRandomness::on_chain_storage_version(), 1);
+	});
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the XCM configuration of the runtime.
+
+mod common;
+
+use canvas_runtime::{
+	relay, xcm_assets::FOREIGN_ASSET_ID_OFFSET, BaseCallFilter, Call, ContractCallerToAccountId,
+	LocalOriginToLocation, LocalXcmExecuteFilter, LocationToAccountId, Origin, PolkadotXcm,
+	RelayOrSiblingLocation, Runtime, UNIT,
+};
+use codec::Encode;
+use common::{new_test_ext, ALICE};
+use frame_support::traits::{Contains, EnsureOrigin};
+use xcm::latest::prelude::*;
+use xcm_builder::EnsureXcmOrigin;
+use xcm_executor::traits::Convert;
+
+type SendOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+
+#[test]
+fn signed_origin_sends_from_its_account() {
+	let location = SendOrigin::ensure_origin(Origin::signed(ALICE)).unwrap();
+
+	assert_eq!(
+		location,
+		Junction::AccountId32 { network: relay::network(), id: ALICE.into() }.into()
+	);
+}
+
+#[test]
+fn unsigned_origins_cannot_send() {
+	assert!(SendOrigin::ensure_origin(Origin::root()).is_err());
+	assert!(SendOrigin::ensure_origin(Origin::none()).is_err());
+}
+
+#[test]
+fn foreign_asset_ids_are_reserved() {
+	let create =
+		|id| Call::Assets(pallet_assets::Call::create { id, admin: ALICE.into(), min_balance: 1 });
+
+	assert!(BaseCallFilter::contains(&create(FOREIGN_ASSET_ID_OFFSET - 1)));
+	assert!(!BaseCallFilter::contains(&create(FOREIGN_ASSET_ID_OFFSET)));
+}
+
+fn relay_token(amount: u128) -> MultiAsset {
+	(MultiLocation::parent(), amount).into()
+}
+
+fn executable(message: Vec<Instruction<Call>>) -> bool {
+	LocalXcmExecuteFilter::contains(&(MultiLocation::here(), Xcm(message)))
+}
+
+#[test]
+fn teleports_are_filtered() {
+	type TeleportFilter = <Runtime as pallet_xcm::Config>::XcmTeleportFilter;
+
+	assert!(!TeleportFilter::contains(&(MultiLocation::here(), vec![relay_token(UNIT)])));
+}
+
+#[test]
+fn reserve_transfers_are_filtered() {
+	type ReserveTransferFilter = <Runtime as pallet_xcm::Config>::XcmReserveTransferFilter;
+
+	assert!(!ReserveTransferFilter::contains(&(MultiLocation::here(), vec![relay_token(UNIT)])));
+}
+
+#[test]
+fn execute_allows_transfers() {
+	let beneficiary: MultiLocation =
+		Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+
+	assert!(executable(vec![
+		WithdrawAsset(relay_token(UNIT).into()),
+		DepositAsset { assets: All.into(), max_assets: 1, beneficiary },
+	]));
+}
+
+#[test]
+fn execute_filters_teleports_reserve_transfers_and_transact() {
+	let transact = || Transact {
+		origin_type: OriginKind::SovereignAccount,
+		require_weight_at_most: 1_000_000,
+		call: Call::System(frame_system::Call::remark { remark: Vec::new() }).encode().into(),
+	};
+
+	assert!(!executable(vec![ReceiveTeleportedAsset(relay_token(UNIT).into())]));
+	assert!(!executable(vec![
+		WithdrawAsset(relay_token(UNIT).into()),
+		InitiateTeleport { assets: All.into(), dest: MultiLocation::parent(), xcm: Xcm(vec![]) },
+	]));
+	assert!(!executable(vec![
+		WithdrawAsset(relay_token(UNIT).into()),
+		DepositReserveAsset {
+			assets: All.into(),
+			max_assets: 1,
+			dest: MultiLocation::parent(),
+			xcm: Xcm(vec![]),
+		},
+	]));
+	assert!(!executable(vec![TransferReserveAsset {
+		assets: relay_token(UNIT).into(),
+		dest: MultiLocation::parent(),
+		xcm: Xcm(vec![]),
+	}]));
+	assert!(!executable(vec![transact()]));
+	assert!(!executable(vec![SetAppendix(Xcm(vec![transact()]))]));
+	assert!(!executable(vec![SetErrorHandler(Xcm(vec![transact()]))]));
+}
+
+#[test]
+fn filtered_messages_are_not_executed() {
+	new_test_ext().execute_with(|| {
+		let message = Xcm(vec![ReceiveTeleportedAsset(relay_token(UNIT).into())]);
+
+		assert!(PolkadotXcm::execute(
+			Origin::signed(ALICE),
+			Box::new(VersionedXcm::V2(message)),
+			1_000_000_000
+		)
+		.is_err());
+	});
+}
+
+fn account_on(interior: Junctions) -> MultiLocation {
+	let account = Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() };
+	MultiLocation::new(1, interior.pushed_with(account).unwrap())
+}
+
+#[test]
+fn only_relay_and_siblings_call_contracts() {
+	let sibling = MultiLocation::new(1, X1(Parachain(2000)));
+
+	assert!(RelayOrSiblingLocation::contains(&MultiLocation::parent()));
+	assert!(RelayOrSiblingLocation::contains(&sibling));
+	assert!(RelayOrSiblingLocation::contains(&account_on(Here)));
+	assert!(RelayOrSiblingLocation::contains(&account_on(X1(Parachain(2000)))));
+
+	assert!(!RelayOrSiblingLocation::contains(&MultiLocation::here()));
+	assert!(!RelayOrSiblingLocation::contains(&MultiLocation::grandparent()));
+	assert!(!RelayOrSiblingLocation::contains(&MultiLocation::new(
+		1,
+		X2(Parachain(2000), PalletInstance(50))
+	)));
+}
+
+#[test]
+fn contract_callers_do_not_change_asset_ownership() {
+	let sibling = MultiLocation::new(1, X1(Parachain(2000)));
+	let remote_account = account_on(X1(Parachain(2000)));
+
+	assert_eq!(
+		ContractCallerToAccountId::convert_ref(&MultiLocation::parent()),
+		LocationToAccountId::convert_ref(&MultiLocation::parent())
+	);
+	assert_eq!(
+		ContractCallerToAccountId::convert_ref(&sibling),
+		LocationToAccountId::convert_ref(&sibling)
+	);
+	// Only contract callers get an account for the location of a remote account. It doesn't
+	// collide with the local account of the same key.
+	assert!(LocationToAccountId::convert_ref(&remote_account).is_err());
+	assert_ne!(ContractCallerToAccountId::convert_ref(&remote_account), Ok(ALICE));
+}
@@ -0,0 +1,42 @@
+#!/usr/bin/env bash
+
+# Generates weight files in `runtime/src/weights`.
+#
+# Usage: ./scripts/benchmark.sh [pallet ...]
+#
+# Without arguments all pallets listed below are benchmarked. A generated file is only used
+# once it is declared in a `weights` module of the runtime and configured as the `WeightInfo`
+# of its pallet. Until then the runtime uses the reference weights shipped with the pallets.
+# The node must have been built with `cargo build --release --features runtime-benchmarks` and
+# the script should be run on the reference hardware.
+
+set -e
+
+PALLETS=("$@")
+if [ ${#PALLETS[@]} -eq 0 ]; then
+	PALLETS=(
+		frame_system
+		pallet_collator_selection
+		pallet_contracts
+		pallet_contracts_scheduler
+		pallet_session
+		pallet_timestamp
+	)
+fi
+
+mkdir -p ./runtime/src/weights
+
+for PALLET in "${PALLETS[@]}"; do
+	echo "*** Benchmarking $PALLET"
+
+	./target/release/canvas benchmark \
+		--chain=dev \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--pallet="$PALLET" \
+		--extrinsic='*' \
+		--steps=50 \
+		--repeat=20 \
+		--header=./FILE_HEADER \
+		--output="./runtime/src/weights/$PALLET.rs"
+done
@@ -0,0 +1,107 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A simulated network of a relay chain, the Canvas runtime and a sibling parachain.
+//!
+//! Messages sent by one chain are executed on the receiving chain with the next
+//! `execute_with` call. The siblings are minimal runtimes which only send messages, one of
+//! them has the parachain id of Statemint and hence is the reserve of the assets it sends.
+
+pub mod relay_chain;
+pub mod sibling;
+
+use canvas_runtime::{BalancesConfig, BuildStorage, GenesisConfig, ParachainInfoConfig, UNIT};
+use polkadot_parachain::primitives::Sibling as SiblingId;
+use sp_runtime::{traits::AccountIdConversion, AccountId32};
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+/// The parachain id of Canvas in the network.
+pub const CANVAS_ID: u32 = 2002;
+/// The parachain id of the sibling in the network.
+pub const SIBLING_ID: u32 = 2000;
+/// The parachain id of Statemint, see `canvas_runtime::StatemintLocation`.
+pub const STATEMINT_ID: u32 = 1000;
+
+pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+pub const INITIAL_BALANCE: u128 = 1_000 * UNIT;
+
+decl_test_parachain! {
+	pub struct Canvas {
+		Runtime = canvas_runtime::Runtime,
+		XcmpMessageHandler = canvas_runtime::XcmpQueue,
+		DmpMessageHandler = canvas_runtime::DmpQueue,
+		new_ext = canvas_ext(),
+	}
+}
+
+decl_test_parachain! {
+	pub struct Sibling {
+		Runtime = sibling::Runtime,
+		XcmpMessageHandler = sibling::DropMessages,
+		DmpMessageHandler = sibling::DropMessages,
+		new_ext = sibling::new_ext(),
+	}
+}
+
+decl_test_parachain! {
+	pub struct Statemint {
+		Runtime = sibling::Runtime,
+		XcmpMessageHandler = sibling::DropMessages,
+		DmpMessageHandler = sibling::DropMessages,
+		new_ext = sibling::new_ext(),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay_chain::Runtime,
+		XcmConfig = relay_chain::XcmConfig,
+		new_ext = relay_chain::new_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct MockNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(SIBLING_ID, Sibling),
+			(STATEMINT_ID, Statemint),
+			(CANVAS_ID, Canvas),
+		],
+	}
+}
+
+/// The sovereign account of the sibling on Canvas.
+pub fn sibling_account() -> AccountId32 {
+	SiblingId(SIBLING_ID.into()).into_account()
+}
+
+/// Canvas with `ALICE` and the sibling's sovereign account endowed.
+pub fn canvas_ext() -> sp_io::TestExternalities {
+	let storage = GenesisConfig {
+		balances: BalancesConfig {
+			balances: vec![(ALICE, INITIAL_BALANCE), (sibling_account(), INITIAL_BALANCE)],
+		},
+		parachain_info: ParachainInfoConfig { parachain_id: CANVAS_ID.into() },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| canvas_runtime::System::set_block_number(1));
+	ext
+}
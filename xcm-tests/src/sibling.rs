@@ -0,0 +1,105 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A minimal sibling parachain which sends messages to Canvas.
+//!
+//! The runtime is shared by all siblings in the network, they only differ in the router which
+//! determines the origin of their messages.
+
+use cumulus_primitives_core::{DmpMessageHandler, ParaId, RelayBlockNumber, XcmpMessageHandler};
+use frame_support::{construct_runtime, parameter_types, traits::Everything, weights::Weight};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32, BuildStorage};
+
+pub type AccountId = AccountId32;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub SiblingId: ParaId = super::SIBLING_ID.into();
+	pub StatemintId: ParaId = super::STATEMINT_ID.into();
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+/// Routes messages of the sibling through the simulated network.
+pub type XcmRouter = super::ParachainXcmRouter<SiblingId>;
+
+/// Routes messages of Statemint through the simulated network.
+pub type StatemintXcmRouter = super::ParachainXcmRouter<StatemintId>;
+
+/// The sibling only sends messages, everything it receives is dropped.
+pub struct DropMessages;
+
+impl XcmpMessageHandler for DropMessages {
+	fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
+		_: I,
+		_: Weight,
+	) -> Weight {
+		0
+	}
+}
+
+impl DmpMessageHandler for DropMessages {
+	fn handle_dmp_messages(
+		_: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
+		_: Weight,
+	) -> Weight {
+		0
+	}
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+	}
+);
+
+pub fn new_ext() -> sp_io::TestExternalities {
+	let storage = GenesisConfig::default().build_storage().unwrap();
+	sp_io::TestExternalities::new(storage)
+}
@@ -0,0 +1,227 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A sibling parachain calls and instantiates a contract on Canvas via XCM `Transact`.
+
+use canvas_runtime::{AccountId, Call, Contracts, Event, Hash, System, UNIT};
+use canvas_xcm_tests::{
+	sibling, sibling_account, Canvas, MockNet, Sibling, ALICE, CANVAS_ID, SIBLING_ID,
+};
+use codec::Encode;
+use frame_support::weights::Weight;
+use pallet_contracts_primitives::Code;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+const GAS_LIMIT: Weight = 5_000_000_000;
+/// Covers the `gas_limit` and the base weight of the call.
+const TRANSACT_WEIGHT: Weight = GAS_LIMIT + 2_000_000_000;
+/// More than enough to buy the weight of the messages.
+const FEE: u128 = 10 * UNIT;
+
+/// A contract with the ABI of ink!'s `flipper` example.
+///
+/// It dispatches on the selectors of the `flip` and `get` messages and stores the flipped
+/// value as a SCALE encoded `bool` under the zero key.
+const FLIPPER: &str = r#"
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_get_storage" (func $seal_get_storage (param i32 i32 i32) (result i32)))
+	(import "seal0" "seal_set_storage" (func $seal_set_storage (param i32 i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) the storage key
+	;; [32, 36) the size of the input buffer
+	;; [36, 40) the input: a message selector
+	;; [40, 44) the size of the value buffer
+	;; [44, 45) the value
+
+	(func $store (param $value i32)
+		(i32.store8 (i32.const 44) (local.get $value))
+		(call $seal_set_storage (i32.const 0) (i32.const 44) (i32.const 1))
+	)
+
+	(func $load (result i32)
+		(i32.store (i32.const 40) (i32.const 1))
+		(drop (call $seal_get_storage (i32.const 0) (i32.const 44) (i32.const 40)))
+		(i32.load8_u (i32.const 44))
+	)
+
+	(func (export "deploy")
+		(call $store (i32.const 0))
+	)
+
+	(func (export "call")
+		(local $selector i32)
+		(i32.store (i32.const 32) (i32.const 4))
+		(call $seal_input (i32.const 36) (i32.const 32))
+		(local.set $selector (i32.load (i32.const 36)))
+
+		;; `flip()`
+		(if (i32.eq (local.get $selector) (i32.const 0x51a53a63))
+			(then
+				(call $store (i32.eqz (call $load)))
+				(return)
+			)
+		)
+		;; `get()`
+		(if (i32.eq (local.get $selector) (i32.const 0xd95b862f))
+			(then
+				(drop (call $load))
+				(call $seal_return (i32.const 0) (i32.const 44) (i32.const 1))
+			)
+		)
+		unreachable
+	)
+)
+"#;
+
+/// The selector of `flip()`.
+const FLIP: [u8; 4] = [0x63, 0x3a, 0xa5, 0x51];
+
+fn upload_flipper() -> Hash {
+	let code = wat::parse_str(FLIPPER).unwrap();
+	Contracts::bare_upload_code(ALICE, code, None).unwrap().code_hash
+}
+
+fn instantiate_flipper() -> AccountId {
+	let code = wat::parse_str(FLIPPER).unwrap();
+	Contracts::bare_instantiate(
+		ALICE,
+		UNIT,
+		GAS_LIMIT,
+		None,
+		Code::Upload(code.into()),
+		Vec::new(),
+		Vec::new(),
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+fn flipper_value(contract: AccountId) -> Option<Vec<u8>> {
+	Contracts::get_storage(contract, [0u8; 32]).unwrap()
+}
+
+/// Pay for the execution on Canvas from the sibling's sovereign account and transact `call`.
+fn transact(call: pallet_xcm_contracts::Call<canvas_runtime::Runtime>) -> Xcm<()> {
+	let call = Call::XcmContracts(call);
+	Xcm(vec![
+		WithdrawAsset((Parent, FEE).into()),
+		BuyExecution { fees: (Parent, FEE).into(), weight_limit: Unlimited },
+		Transact {
+			origin_type: OriginKind::Xcm,
+			require_weight_at_most: TRANSACT_WEIGHT,
+			call: call.encode().into(),
+		},
+		RefundSurplus,
+		DepositAsset {
+			assets: All.into(),
+			max_assets: 1,
+			beneficiary: (Parent, Parachain(SIBLING_ID)).into(),
+		},
+	])
+}
+
+fn send_to_canvas(message: Xcm<()>) {
+	Sibling::execute_with(|| {
+		assert_eq!(
+			<sibling::XcmRouter as SendXcm>::send_xcm((Parent, Parachain(CANVAS_ID)), message),
+			Ok(())
+		);
+	});
+}
+
+fn sibling_location() -> MultiLocation {
+	(Parent, Parachain(SIBLING_ID)).into()
+}
+
+#[test]
+fn sibling_calls_contract() {
+	MockNet::reset();
+	let contract = Canvas::execute_with(instantiate_flipper);
+
+	send_to_canvas(transact(pallet_xcm_contracts::Call::call {
+		dest: contract.clone().into(),
+		value: 0,
+		gas_limit: GAS_LIMIT,
+		storage_deposit_limit: None,
+		data: FLIP.to_vec(),
+	}));
+
+	Canvas::execute_with(|| {
+		assert_eq!(flipper_value(contract.clone()), Some(vec![1]));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::XcmContracts(pallet_xcm_contracts::Event::Called {
+				origin: sibling_location(),
+				caller: sibling_account(),
+				contract: contract.clone(),
+			})));
+	});
+}
+
+#[test]
+fn sibling_instantiates_contract() {
+	MockNet::reset();
+	let code_hash = Canvas::execute_with(upload_flipper);
+
+	send_to_canvas(transact(pallet_xcm_contracts::Call::instantiate {
+		value: UNIT,
+		gas_limit: GAS_LIMIT,
+		storage_deposit_limit: None,
+		code_hash,
+		data: Vec::new(),
+		salt: Vec::new(),
+	}));
+
+	Canvas::execute_with(|| {
+		let contract = Contracts::contract_address(&sibling_account(), &code_hash, &[]);
+		assert_eq!(flipper_value(contract.clone()), Some(vec![0]));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::XcmContracts(pallet_xcm_contracts::Event::Instantiated {
+				origin: sibling_location(),
+				deployer: sibling_account(),
+				contract: contract.clone(),
+			})));
+	});
+}
+
+#[test]
+fn unpaid_transact_is_rejected() {
+	MockNet::reset();
+	let contract = Canvas::execute_with(instantiate_flipper);
+
+	send_to_canvas(Xcm(vec![Transact {
+		origin_type: OriginKind::Xcm,
+		require_weight_at_most: TRANSACT_WEIGHT,
+		call: Call::XcmContracts(pallet_xcm_contracts::Call::call {
+			dest: contract.clone().into(),
+			value: 0,
+			gas_limit: GAS_LIMIT,
+			storage_deposit_limit: None,
+			data: FLIP.to_vec(),
+		})
+		.encode()
+		.into(),
+	}]));
+
+	Canvas::execute_with(|| {
+		assert_eq!(flipper_value(contract), Some(vec![0]));
+	});
+}
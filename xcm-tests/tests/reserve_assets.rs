@@ -0,0 +1,115 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Assets which are reserve-backed by Statemint are deposited on Canvas and pay for XCM fees.
+
+use canvas_runtime::{
+	xcm_assets::FOREIGN_ASSET_ID_OFFSET, AssetId, Assets, AssetsFeeReceiver, Origin,
+};
+use canvas_xcm_tests::{
+	sibling, Canvas, MockNet, Sibling, Statemint, ALICE, CANVAS_ID, STATEMINT_ID,
+};
+use frame_support::assert_ok;
+use sp_runtime::AccountId32;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+const BOB: AccountId32 = AccountId32::new([2u8; 32]);
+
+/// The id of the asset on Statemint.
+const USDT: u32 = 1984;
+/// The id of the asset on Canvas.
+const LOCAL_USDT: AssetId = FOREIGN_ASSET_ID_OFFSET + USDT;
+const MIN_BALANCE: u128 = 1_000;
+const AMOUNT: u128 = 1_000_000;
+
+fn register_usdt() {
+	Canvas::execute_with(|| {
+		assert_ok!(Assets::force_create(
+			Origin::root(),
+			LOCAL_USDT,
+			ALICE.into(),
+			true,
+			MIN_BALANCE
+		));
+	});
+}
+
+/// `AMOUNT` of USDT deposited to `BOB`, paying the fees with the same asset.
+fn deposit_usdt() -> Xcm<()> {
+	let usdt: MultiAsset = (
+		MultiLocation::new(
+			1,
+			X3(Parachain(STATEMINT_ID), PalletInstance(50), GeneralIndex(USDT.into())),
+		),
+		AMOUNT,
+	)
+		.into();
+	Xcm(vec![
+		ReserveAssetDeposited(usdt.clone().into()),
+		ClearOrigin,
+		BuyExecution { fees: usdt, weight_limit: Unlimited },
+		DepositAsset {
+			assets: All.into(),
+			max_assets: 1,
+			beneficiary: Junction::AccountId32 { network: NetworkId::Any, id: BOB.into() }.into(),
+		},
+	])
+}
+
+#[test]
+fn reserve_asset_pays_for_its_deposit() {
+	MockNet::reset();
+	register_usdt();
+
+	Statemint::execute_with(|| {
+		assert_eq!(
+			<sibling::StatemintXcmRouter as SendXcm>::send_xcm(
+				(Parent, Parachain(CANVAS_ID)),
+				deposit_usdt()
+			),
+			Ok(())
+		);
+	});
+
+	Canvas::execute_with(|| {
+		let deposited = Assets::balance(LOCAL_USDT, &BOB);
+		let fee = Assets::balance(LOCAL_USDT, &AssetsFeeReceiver::get());
+		assert!(deposited > 0);
+		assert!(fee > 0);
+		assert_eq!(deposited + fee, AMOUNT);
+	});
+}
+
+#[test]
+fn asset_is_only_accepted_from_its_reserve() {
+	MockNet::reset();
+	register_usdt();
+
+	Sibling::execute_with(|| {
+		assert_eq!(
+			<sibling::XcmRouter as SendXcm>::send_xcm(
+				(Parent, Parachain(CANVAS_ID)),
+				deposit_usdt()
+			),
+			Ok(())
+		);
+	});
+
+	Canvas::execute_with(|| {
+		assert_eq!(Assets::balance(LOCAL_USDT, &BOB), 0);
+	});
+}
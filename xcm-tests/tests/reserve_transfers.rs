@@ -0,0 +1,58 @@
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Canvas isn't the reserve of the relay chain token, so it can't be reserve transferred out.
+
+use canvas_runtime::{Balances, Origin, PolkadotXcm, Runtime, UNIT};
+use canvas_xcm_tests::{Canvas, MockNet, ALICE, INITIAL_BALANCE};
+use frame_support::assert_noop;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+fn beneficiary() -> MultiLocation {
+	Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into()
+}
+
+#[test]
+fn relay_token_reserve_transfer_is_rejected() {
+	MockNet::reset();
+
+	Canvas::execute_with(|| {
+		assert_noop!(
+			PolkadotXcm::reserve_transfer_assets(
+				Origin::signed(ALICE),
+				Box::new(MultiLocation::parent().into()),
+				Box::new(beneficiary().into()),
+				Box::new((MultiLocation::parent(), UNIT).into()),
+				0,
+			),
+			pallet_xcm::Error::<Runtime>::Filtered
+		);
+		assert_noop!(
+			PolkadotXcm::limited_reserve_transfer_assets(
+				Origin::signed(ALICE),
+				Box::new(MultiLocation::parent().into()),
+				Box::new(beneficiary().into()),
+				Box::new((MultiLocation::parent(), UNIT).into()),
+				0,
+				Unlimited,
+			),
+			pallet_xcm::Error::<Runtime>::Filtered
+		);
+
+		assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE);
+	});
+}